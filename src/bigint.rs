@@ -0,0 +1,421 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+const BASE: u64 = 1_000_000_000;
+
+/// An arbitrary-precision signed integer. Integer arithmetic switches to this
+/// whenever a result no longer fits in an `i64`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    /// Magnitude in base 10^9, least significant limb first, without leading
+    /// zero limbs. Zero has no limbs and is never negative.
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn from_i64(n: i64) -> Self {
+        let mut magnitude = n.unsigned_abs();
+        let mut limbs = Vec::new();
+
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE) as u32);
+            magnitude /= BASE;
+        }
+
+        BigInt { negative: n < 0, limbs }
+    }
+
+    /// Parses an optionally `-`-prefixed string of decimal digits.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut limbs = Vec::new();
+        let mut end = digits.len();
+
+        while end > 0 {
+            let start = end.saturating_sub(9);
+            limbs.push(digits[start..end].parse::<u32>().ok()?);
+            end = start;
+        }
+
+        Some(BigInt::from_parts(negative, limbs))
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> Self {
+        trim(&mut limbs);
+        let negative = negative && !limbs.is_empty();
+        BigInt { negative, limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// A number of bits that is always enough to hold the magnitude. Each
+    /// limb is below 10^9 < 2^30.
+    pub fn bits_upper_bound(&self) -> u64 {
+        self.limbs.len() as u64 * 30
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        // Three limbs hold values below 10^27, which always fit in an i128
+        if self.limbs.len() > 3 {
+            return None;
+        }
+
+        let magnitude = self.limbs.iter().rev().fold(0i128, |acc, &limb| acc * BASE as i128 + limb as i128);
+        let value = if self.negative { -magnitude } else { magnitude };
+
+        i64::try_from(value).ok()
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self.limbs.iter().rev().fold(0.0, |acc, &limb| acc * BASE as f64 + limb as f64);
+
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    /// Truncating division and remainder, matching `i64`'s `/` and `%`.
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        assert!(!divisor.is_zero(), "BigInt division by zero");
+
+        let (quotient, remainder) = div_rem_magnitude(&self.limbs, &divisor.limbs);
+
+        (
+            BigInt::from_parts(self.negative != divisor.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        )
+    }
+
+    /// Division rounding toward negative infinity.
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_floor(&self, divisor: &BigInt) -> BigInt {
+        let (quotient, remainder) = self.div_rem(divisor);
+
+        if !remainder.is_zero() && remainder.negative != divisor.negative {
+            &quotient - &BigInt::from_i64(1)
+        } else {
+            quotient
+        }
+    }
+
+    pub fn pow(&self, mut exponent: u64) -> BigInt {
+        let mut result = BigInt::from_i64(1);
+        let mut base = self.clone();
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+
+            exponent >>= 1;
+
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.limbs, &other.limbs));
+        }
+
+        match compare_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Less => BigInt::from_parts(other.negative, sub_magnitude(&other.limbs, &self.limbs)),
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.limbs, &other.limbs)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != other.negative, mul_magnitude(&self.limbs, &other.limbs))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_magnitude(&self.limbs, &other.limbs),
+            (true, true) => compare_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((most_significant, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+
+        write!(f, "{}", most_significant)?;
+
+        for limb in rest.iter().rev() {
+            write!(f, "{:09}", limb)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn trim(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn compare_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0u64;
+
+    for i in 0..a.len().max(b.len()) {
+        let sum = carry + *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64;
+        result.push((sum % BASE) as u32);
+        carry = sum / BASE;
+    }
+
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+
+    result
+}
+
+/// Computes `a - b`; the caller guarantees `a >= b`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+
+    for (i, &limb) in a.iter().enumerate() {
+        let mut difference = limb as i64 - borrow - *b.get(i).unwrap_or(&0) as i64;
+
+        if difference < 0 {
+            difference += BASE as i64;
+            borrow = 1;
+        } else {
+            borrow = 0;
+        }
+
+        result.push(difference as u32);
+    }
+
+    trim(&mut result);
+    result
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut result = vec![0u64; a.len() + b.len()];
+
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+
+        for (j, &y) in b.iter().enumerate() {
+            let current = result[i + j] + x as u64 * y as u64 + carry;
+            result[i + j] = current % BASE;
+            carry = current / BASE;
+        }
+
+        let mut k = i + b.len();
+
+        while carry > 0 {
+            let current = result[k] + carry;
+            result[k] = current % BASE;
+            carry = current / BASE;
+            k += 1;
+        }
+    }
+
+    let mut limbs: Vec<u32> = result.into_iter().map(|limb| limb as u32).collect();
+    trim(&mut limbs);
+    limbs
+}
+
+/// Schoolbook long division, one base-10^9 digit at a time. Each quotient
+/// digit is found by binary search, which is slow but simple.
+fn div_rem_magnitude(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder: Vec<u32> = Vec::new();
+
+    for i in (0..a.len()).rev() {
+        remainder.insert(0, a[i]);
+        trim(&mut remainder);
+
+        let (mut low, mut high) = (0u32, (BASE - 1) as u32);
+
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+
+            if compare_magnitude(&mul_magnitude(b, &[mid]), &remainder) == Ordering::Greater {
+                high = mid - 1;
+            } else {
+                low = mid;
+            }
+        }
+
+        if low > 0 {
+            remainder = sub_magnitude(&remainder, &mul_magnitude(b, &[low]));
+        }
+
+        quotient[i] = low;
+    }
+
+    trim(&mut quotient);
+    (quotient, remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(text: &str) -> BigInt {
+        BigInt::parse(text).unwrap()
+    }
+
+    #[test]
+    fn add_carries_across_limbs() {
+        assert_eq!(&big("999999999") + &big("1"), big("1000000000"));
+        assert_eq!(&big("999999999999999999") + &big("1"), big("1000000000000000000"));
+        assert_eq!(&big("-999999999999999999") + &big("-1"), big("-1000000000000000000"));
+    }
+
+    #[test]
+    fn sub_borrows_across_limbs() {
+        assert_eq!(&big("1000000000") - &big("1"), big("999999999"));
+        assert_eq!(&big("1000000000000000000") - &big("1"), big("999999999999999999"));
+        assert_eq!(&big("1") - &big("1000000000"), big("-999999999"));
+        assert_eq!(&big("1000000000") - &big("1000000000"), big("0"));
+    }
+
+    #[test]
+    fn mul_carries_across_limbs() {
+        assert_eq!(&big("999999999") * &big("999999999"), big("999999998000000001"));
+        assert_eq!(&big("-1000000000") * &big("1000000000"), big("-1000000000000000000"));
+        assert_eq!(&big("123456789") * &big("0"), big("0"));
+    }
+
+    #[test]
+    fn div_rem_truncates_like_i64() {
+        for (a, b) in [(7, 2), (-7, 2), (7, -2), (-7, -2), (8, -2), (0, 5)] {
+            let (quotient, remainder) = BigInt::from_i64(a).div_rem(&BigInt::from_i64(b));
+            assert_eq!(quotient, BigInt::from_i64(a / b), "{} / {}", a, b);
+            assert_eq!(remainder, BigInt::from_i64(a % b), "{} % {}", a, b);
+        }
+    }
+
+    #[test]
+    fn div_rem_of_multi_limb_values() {
+        let (quotient, remainder) = big("1000000000000000000000000000007").div_rem(&big("1000000000000000"));
+        assert_eq!(quotient, big("1000000000000000"));
+        assert_eq!(remainder, big("7"));
+
+        let (quotient, remainder) = big("-1000000000000000000000000000007").div_rem(&big("999999999"));
+        assert_eq!(quotient, big("-1000000001000000001000"));
+        assert_eq!(remainder, big("-1007"));
+    }
+
+    #[test]
+    fn div_floor_rounds_toward_negative_infinity() {
+        for (a, b, expected) in [(7, 2, 3), (-7, 2, -4), (7, -2, -4), (-7, -2, 3), (-8, 2, -4), (8, -2, -4)] {
+            let quotient = BigInt::from_i64(a).div_floor(&BigInt::from_i64(b));
+            assert_eq!(quotient, BigInt::from_i64(expected), "{} ~/ {}", a, b);
+        }
+
+        let quotient = big("-1000000000000000000000000000007").div_floor(&big("999999999"));
+        assert_eq!(quotient, big("-1000000001000000001001"));
+    }
+
+    #[test]
+    fn to_i64_at_the_limits() {
+        assert_eq!(BigInt::from_i64(i64::MAX).to_i64(), Some(i64::MAX));
+        assert_eq!(BigInt::from_i64(i64::MIN).to_i64(), Some(i64::MIN));
+        assert_eq!(big("9223372036854775808").to_i64(), None);
+        assert_eq!(big("-9223372036854775809").to_i64(), None);
+        assert_eq!(big("-9223372036854775808").to_i64(), Some(i64::MIN));
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        for text in ["0", "7", "-7", "1000000000", "-999999999", "123456789012345678901234567890"] {
+            assert_eq!(big(text).to_string(), text);
+        }
+
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(big("-0").to_string(), "0");
+        assert_eq!(big("1000000001").to_string(), "1000000001");
+    }
+
+    #[test]
+    fn parse_rejects_non_numbers() {
+        for text in ["", "-", "+5", "12a", "1.5", "--1"] {
+            assert!(BigInt::parse(text).is_none(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn pow_and_ordering() {
+        assert_eq!(big("2").pow(100), big("1267650600228229401496703205376"));
+        assert_eq!(big("-3").pow(3), big("-27"));
+        assert_eq!(big("5").pow(0), big("1"));
+
+        assert!(big("-5") < big("3"));
+        assert!(big("-10") < big("-9"));
+        assert!(big("1000000000") > big("999999999"));
+    }
+}
@@ -1,48 +1,343 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+use crate::bigint::BigInt;
+use crate::interpreter::Interpreter;
+use crate::parser::Stmt;
 use crate::token::Literal;
 
 #[derive(Debug, Clone)]
 pub enum Value {
     Literal(Literal),
     Function(Function),
-    Array(Vec<Value>),
+    NativeFunction(NativeFunction),
+    /// Copies share their elements until one of them is written to.
+    Array(Rc<Vec<Value>>),
+    /// Copied like arrays: copies share their entries until one of them is
+    /// written to.
+    Map(Rc<Map>),
+    Module(Module),
+    /// A `squad` declaration, called like a function to build records.
+    RecordType(Rc<RecordType>),
+    Record(Record),
+    /// A `clique` declaration, called like a function to build instances.
+    Class(Rc<Class>),
+    /// Unlike records, instances are shared: every copy sees the same fields.
+    Instance(Rc<RefCell<Instance>>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Function {
     pub name: String,
     pub params: Vec<String>,
-    pub body: Box<crate::parser::Expr>,
+    pub body: Rc<Vec<Stmt>>,
+    /// The scope the function was defined in, kept alive for as long as the
+    /// function value is.
+    pub closure: Rc<RefCell<Environment>>,
+    /// The file the function was written in, which `summon`s inside it are
+    /// relative to wherever it is called from.
+    pub file: Option<Rc<Path>>,
+    /// Set for a clique's `spawn` method, which always returns `me`.
+    pub is_initializer: bool,
 }
 
-impl Value {
-    pub fn to_string(&self) -> String {
-        match self {
-            Value::Literal(lit) => match lit {
-                Literal::String(s) => s.clone(),
-                Literal::Number(n) => n.to_string(),
-                Literal::Boolean(b) => b.to_string(),
-                Literal::Nil => "nil".to_string(),
+impl Function {
+    /// Makes a method callable on `instance` by wrapping its closure in a
+    /// scope where `me` is that instance.
+    pub fn bind(&self, instance: Value) -> Function {
+        let mut environment = Environment::new_enclosed(Rc::clone(&self.closure));
+        environment.define("me".to_string(), instance);
+
+        Function {
+            closure: Rc::new(RefCell::new(environment)),
+            ..self.clone()
+        }
+    }
+}
+
+impl std::fmt::Debug for Function {
+    // The captured scope usually contains the function itself, so it is left
+    // out to avoid recursing forever.
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Function")
+            .field("name", &self.name)
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Key-value pairs in insertion order, indexed by key so that lookups don't
+/// have to scan the entries.
+#[derive(Debug, Clone, Default)]
+pub struct Map {
+    /// Removed entries leave a `None` behind until the next compaction, so
+    /// removing doesn't shift everything after it.
+    entries: Vec<Option<(Value, Value)>>,
+    positions: HashMap<MapKey, usize>,
+    len: usize,
+}
+
+/// A map key with numbers normalised, so keys that are `==` hash the same
+/// way: `1`, `1.0` and `-0.0 + 1` are all one key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum MapKey {
+    String(String),
+    Integer(i64),
+    BigInt(BigInt),
+    /// The bits of a float that isn't a whole number.
+    Float(u64),
+    Boolean(bool),
+}
+
+impl MapKey {
+    /// Returns `None` for NaN, which is never `==` to anything, itself
+    /// included, so it can't be found again once inserted.
+    fn new(key: &Value) -> Result<Option<MapKey>, String> {
+        let key = match key {
+            Value::Literal(Literal::String(s)) => MapKey::String(s.clone()),
+            Value::Literal(Literal::Integer(n)) => MapKey::Integer(*n),
+            Value::Literal(Literal::BigInt(n)) => MapKey::BigInt(n.clone()),
+            Value::Literal(Literal::Boolean(b)) => MapKey::Boolean(*b),
+            Value::Literal(Literal::Float(n)) if n.is_nan() => return Ok(None),
+            Value::Literal(Literal::Float(n)) if n.fract() != 0.0 || n.is_infinite() => MapKey::Float(n.to_bits()),
+            // -2^63 is an exact float, so the range check is exact too
+            Value::Literal(Literal::Float(n)) if *n >= i64::MIN as f64 && *n < -(i64::MIN as f64) => {
+                MapKey::Integer(*n as i64)
             },
-            Value::Function(f) => format!("<function {}>", f.name),
+            Value::Literal(Literal::Float(n)) => {
+                MapKey::BigInt(BigInt::parse(&format!("{:.0}", n)).expect("a whole float prints as digits"))
+            },
+            other => return Err(format!("Map keys must be strings, numbers or booleans, got {}.", other)),
+        };
+
+        Ok(Some(key))
+    }
+}
+
+impl Map {
+    pub fn new() -> Self {
+        Map::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Value, Value)> {
+        self.entries.iter().flatten()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &Value> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Finds the value whose key is `==` to `key`.
+    pub fn get(&self, key: &Value) -> Result<Option<&Value>, String> {
+        Ok(self.position(key)?.and_then(|i| self.entries[i].as_ref()).map(|(_, value)| value))
+    }
+
+    pub fn get_mut(&mut self, key: &Value) -> Result<Option<&mut Value>, String> {
+        Ok(self.position(key)?.and_then(|i| self.entries[i].as_mut()).map(|(_, value)| value))
+    }
+
+    /// Sets the value for `key`. An existing entry keeps its place and its
+    /// original key; a new one goes at the end.
+    pub fn insert(&mut self, key: Value, value: Value) -> Result<(), String> {
+        let map_key = MapKey::new(&key)?;
+
+        if let Some(&i) = map_key.as_ref().and_then(|map_key| self.positions.get(map_key))
+            && let Some((_, existing)) = &mut self.entries[i]
+        {
+            *existing = value;
+            return Ok(());
+        }
+
+        if let Some(map_key) = map_key {
+            self.positions.insert(map_key, self.entries.len());
+        }
+
+        self.entries.push(Some((key, value)));
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the entry for `key`, keeping the others in order.
+    pub fn remove(&mut self, key: &Value) -> Result<Option<Value>, String> {
+        let Some(map_key) = MapKey::new(key)? else {
+            return Ok(None);
+        };
+        let Some(i) = self.positions.remove(&map_key) else {
+            return Ok(None);
+        };
+
+        let removed = self.entries[i].take().map(|(_, value)| value);
+        self.len -= 1;
+
+        // Compacting once at least half the slots are empty keeps removal
+        // cheap on average
+        if self.entries.len() >= 2 * self.len + 8 {
+            self.compact();
+        }
+
+        Ok(removed)
+    }
+
+    fn compact(&mut self) {
+        self.entries.retain(Option::is_some);
+
+        for (i, (key, _)) in self.entries.iter().flatten().enumerate() {
+            if let Ok(Some(map_key)) = MapKey::new(key) {
+                self.positions.insert(map_key, i);
+            }
+        }
+    }
+
+    fn position(&self, key: &Value) -> Result<Option<usize>, String> {
+        Ok(MapKey::new(key)?.and_then(|key| self.positions.get(&key).copied()))
+    }
+}
+
+#[derive(Debug)]
+pub struct RecordType {
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
+/// An instance of a `squad`. Like arrays, records are copied on assignment;
+/// `values` holds one value per field, in declaration order.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub record_type: Rc<RecordType>,
+    pub values: Vec<Value>,
+}
+
+impl Record {
+    pub fn field_index(&self, name: &str) -> Result<usize, String> {
+        self.record_type.fields
+            .iter()
+            .position(|field| field == name)
+            .ok_or_else(|| format!("Squad '{}' has no field '{}'.", self.record_type.name, name))
+    }
+}
+
+#[derive(Debug)]
+pub struct Class {
+    pub name: String,
+    pub superclass: Option<Rc<Class>>,
+    pub methods: HashMap<String, Function>,
+}
+
+impl Class {
+    /// Looks `name` up on this clique, then on its parents.
+    pub fn find_method(&self, name: &str) -> Option<Function> {
+        match self.methods.get(name) {
+            Some(method) => Some(method.clone()),
+            None => self.superclass.as_ref().and_then(|parent| parent.find_method(name)),
+        }
+    }
+}
+
+pub struct Instance {
+    pub class: Rc<Class>,
+    pub fields: HashMap<String, Value>,
+}
+
+impl std::fmt::Debug for Instance {
+    // Fields can point back at the instance itself, so they are left out
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Instance")
+            .field("class", &self.class.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A `summon`ed file. Its top-level definitions live in `environment` and are
+/// read with `module.name`.
+#[derive(Clone)]
+pub struct Module {
+    pub name: String,
+    pub environment: Rc<RefCell<Environment>>,
+}
+
+impl std::fmt::Debug for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Module")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A function implemented in Rust, such as `len`.
+#[derive(Debug, Clone)]
+pub struct NativeFunction {
+    pub name: &'static str,
+    pub arity: usize,
+    pub function: fn(&mut Interpreter, Vec<Value>) -> Result<Value, String>,
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::Literal(lit) => write!(f, "{}", lit),
+            Value::Function(function) => write!(f, "<closure {}>", function.name),
+            Value::NativeFunction(native) => write!(f, "<native fn {}>", native.name),
             Value::Array(elements) => {
                 let elements_str: Vec<String> = elements.iter()
                     .map(|e| e.to_string())
                     .collect();
-                format!("[{}]", elements_str.join(", "))
-            }
+                write!(f, "[{}]", elements_str.join(", "))
+            },
+            Value::Map(map) => {
+                // Strings are quoted so `{"1": x}` and `{1: x}` look different
+                let quoted = |value: &Value| match value {
+                    Value::Literal(Literal::String(s)) => format!("{:?}", s),
+                    other => other.to_string(),
+                };
+
+                let entries_str: Vec<String> = map.iter()
+                    .map(|(key, value)| format!("{}: {}", quoted(key), quoted(value)))
+                    .collect();
+                write!(f, "{{{}}}", entries_str.join(", "))
+            },
+            Value::Module(module) => write!(f, "<module {}>", module.name),
+            Value::RecordType(record_type) => write!(f, "<squad {}>", record_type.name),
+            Value::Record(record) => {
+                let fields_str: Vec<String> = record.record_type.fields.iter()
+                    .zip(&record.values)
+                    .map(|(field, value)| format!("{}: {}", field, value))
+                    .collect();
+
+                if fields_str.is_empty() {
+                    write!(f, "{} {{}}", record.record_type.name)
+                } else {
+                    write!(f, "{} {{ {} }}", record.record_type.name, fields_str.join(", "))
+                }
+            },
+            Value::Class(class) => write!(f, "<clique {}>", class.name),
+            Value::Instance(instance) => write!(f, "<{} instance>", instance.borrow().class.name),
         }
     }
 }
 
 pub struct Environment {
     values: HashMap<String, Value>,
+    enclosing: Option<Rc<RefCell<Environment>>>,
 }
 
 impl Environment {
     pub fn new() -> Self {
         Environment {
             values: HashMap::new(),
+            enclosing: None,
+        }
+    }
+
+    /// Creates a child scope whose lookups fall back to `enclosing`.
+    pub fn new_enclosed(enclosing: Rc<RefCell<Environment>>) -> Self {
+        Environment {
+            values: HashMap::new(),
+            enclosing: Some(enclosing),
         }
     }
 
@@ -51,15 +346,98 @@ impl Environment {
     }
 
     pub fn get(&self, name: &str) -> Option<Value> {
+        match self.values.get(name) {
+            Some(value) => Some(value.clone()),
+            None => self.enclosing.as_ref().and_then(|enclosing| enclosing.borrow().get(name)),
+        }
+    }
+
+    /// Runs `update` on the variable's value where it is stored, instead of
+    /// copying the value out and assigning it back.
+    pub fn update(&mut self, name: &str, update: impl FnOnce(&mut Value) -> Result<(), String>) -> Result<(), String> {
+        match self.values.get_mut(name) {
+            Some(value) => update(value),
+            None => match &self.enclosing {
+                Some(enclosing) => enclosing.borrow_mut().update(name, update),
+                None => Err(format!("Undefined variable '{}'.", name)),
+            },
+        }
+    }
+
+    /// Looks `name` up in this scope only, ignoring enclosing ones.
+    pub fn get_local(&self, name: &str) -> Option<Value> {
         self.values.get(name).cloned()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(n: i64) -> Value {
+        Value::Literal(Literal::Integer(n))
+    }
+
+    fn float(n: f64) -> Value {
+        Value::Literal(Literal::Float(n))
+    }
 
-    pub fn assign(&mut self, name: &str, value: Value) -> Result<(), String> {
-        if self.values.contains_key(name) {
-            self.values.insert(name.to_string(), value);
-            Ok(())
-        } else {
-            Err(format!("Undefined variable '{}'.", name))
+    fn string(s: &str) -> Value {
+        Value::Literal(Literal::String(s.to_string()))
+    }
+
+    #[test]
+    fn equal_numbers_are_one_key() {
+        let mut map = Map::new();
+        map.insert(int(1), string("a")).unwrap();
+        map.insert(float(1.0), string("b")).unwrap();
+        map.insert(float(-0.0), string("c")).unwrap();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&int(1)).unwrap().unwrap().to_string(), "b");
+        assert_eq!(map.get(&int(0)).unwrap().unwrap().to_string(), "c");
+        assert!(map.get(&float(1.5)).unwrap().is_none());
+    }
+
+    #[test]
+    fn strings_and_numbers_are_different_keys() {
+        let mut map = Map::new();
+        map.insert(string("1"), string("a")).unwrap();
+        map.insert(int(1), string("b")).unwrap();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(Value::Map(Rc::new(map)).to_string(), r#"{"1": "a", 1: "b"}"#);
+    }
+
+    #[test]
+    fn whole_floats_past_i64_match_big_integers() {
+        let mut map = Map::new();
+        let big = Value::Literal(Literal::BigInt(BigInt::parse("9223372036854775808").unwrap()));
+        map.insert(big, string("big")).unwrap();
+
+        assert!(map.get(&float(9223372036854775808.0)).unwrap().is_some());
+    }
+
+    #[test]
+    fn removing_keeps_the_rest_in_order() {
+        let mut map = Map::new();
+        for n in 0..100 {
+            map.insert(int(n), int(n * 10)).unwrap();
+        }
+        for n in (0..100).filter(|n| n % 3 != 0) {
+            assert!(map.remove(&int(n)).unwrap().is_some());
         }
+
+        assert!(map.remove(&int(1)).unwrap().is_none());
+        let keys: Vec<String> = map.keys().map(Value::to_string).collect();
+        let expected: Vec<String> = (0..100).filter(|n| n % 3 == 0).map(|n| n.to_string()).collect();
+        assert_eq!(keys, expected);
+        assert_eq!(map.get(&int(99)).unwrap().unwrap().to_string(), "990");
+    }
+
+    #[test]
+    fn rejects_unhashable_keys() {
+        let mut map = Map::new();
+        assert!(map.insert(Value::Array(Rc::new(Vec::new())), int(1)).is_err());
     }
 }
@@ -1,20 +1,90 @@
-use crate::parser::{Expr, Stmt};
-use crate::token::{TokenType, Literal};
-use crate::environment::{Environment, Value, Function};
+use crate::bigint::BigInt;
+use crate::lexer::Lexer;
+use crate::native;
+use crate::parser::{Expr, ImportKind, LoopKind, Parser, Pattern, Stmt};
+use crate::token::{Token, TokenType, Literal};
+use crate::environment::{Class, Environment, Function, Instance, Map, Module, Record, RecordType, Value};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
 use std::io::{self, Write, BufRead};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
-pub struct Interpreter<'a> {
-    environment: &'a mut Environment,
+/// An assignment target with its indices already evaluated: where it
+/// starts, and the steps from there to the part being written.
+struct Place {
+    root: PlaceRoot,
+    steps: Vec<Step>,
+}
+
+enum PlaceRoot {
+    Variable(Token),
+    /// A temporary instance, such as the result of a call.
+    Instance(Value),
+}
+
+enum Step {
+    Index(Value),
+    Field(Token),
+}
+
+/// How many paijorot calls may be in progress at once before a call fails
+/// with a stack overflow error.
+const MAX_CALL_DEPTH: usize = 1000;
+
+/// The largest integer, in bits, that `**` and `<<` will build (about
+/// 315,000 decimal digits). Bigger results take long enough to compute that
+/// the program would look hung.
+const MAX_RESULT_BITS: u64 = 1 << 20;
+
+pub struct Interpreter {
+    environment: Rc<RefCell<Environment>>,
     in_loop: bool,
     should_break: bool,
+    should_continue: bool,
+    /// Label of the loop a pending `sybau`/`skibidi` is aimed at, if any.
+    jump_label: Option<String>,
+    in_function: bool,
+    return_value: Option<Value>,
+    /// Number of paijorot function calls currently in progress.
+    call_depth: usize,
+    /// The value of the `crashout` currently unwinding, if any. The `Err`
+    /// travelling alongside it only carries its message.
+    thrown: Option<Value>,
+    /// Line of the most recently evaluated operator or name, reported in the
+    /// error values runtime failures turn into.
+    line: usize,
+    /// The file the running code was written in, which `summon` paths are
+    /// relative to. `None` in the REPL, where they are relative to the
+    /// working directory.
+    file: Option<Rc<Path>>,
+    /// Modules that have already run, by canonical path.
+    modules: HashMap<PathBuf, Module>,
+    /// Files that are part way through running, outermost first. Summoning
+    /// one of these again would be a cycle.
+    importing: Vec<PathBuf>,
 }
 
-impl<'a> Interpreter<'a> {
-    pub fn new(environment: &'a mut Environment) -> Self {
+impl Interpreter {
+    pub fn new(environment: Rc<RefCell<Environment>>, file: Option<PathBuf>) -> Self {
+        let importing = file.iter().filter_map(|file| fs::canonicalize(file).ok()).collect();
+
         Interpreter {
             environment,
             in_loop: false,
             should_break: false,
+            should_continue: false,
+            jump_label: None,
+            in_function: false,
+            return_value: None,
+            call_depth: 0,
+            thrown: None,
+            line: 0,
+            file: file.map(Rc::from),
+            modules: HashMap::new(),
+            importing,
         }
     }
 
@@ -38,7 +108,7 @@ impl<'a> Interpreter<'a> {
             },
             Stmt::Print(expr) => {
                 let value = self.evaluate(expr)?;
-                println!("{}", value.to_string());
+                println!("{}", value);
                 Ok(())
             },
             Stmt::Var(name, initializer) => {
@@ -48,154 +118,665 @@ impl<'a> Interpreter<'a> {
                     Value::Literal(Literal::Nil)
                 };
 
-                self.environment.define(name.lexeme.clone(), value);
+                self.environment.borrow_mut().define(name.lexeme.clone(), value);
                 Ok(())
             },
-            Stmt::If(condition, then_branch, else_branch) => {
-                let condition_value = self.evaluate(condition)?;
+            Stmt::Block(statements) => {
+                self.execute_block(statements, self.new_scope())
+            },
+            Stmt::If(arms, else_branch) => {
+                for (index, (condition, branch)) in arms.iter().enumerate() {
+                    let condition_value = self.evaluate(condition).map_err(|e| {
+                        if arms.len() > 1 {
+                            format!("{} (in arm {} of 'yo' chain)", e, index + 1)
+                        } else {
+                            e
+                        }
+                    })?;
 
-                if self.is_truthy(&condition_value) {
-                    self.execute(then_branch)?;
-                } else if let Some(else_stmt) = else_branch {
+                    if self.is_truthy(&condition_value) {
+                        return self.execute(branch);
+                    }
+                }
+
+                if let Some(else_stmt) = else_branch {
                     self.execute(else_stmt)?;
                 }
 
                 Ok(())
             },
-            Stmt::Loop(condition, body) => {
+            Stmt::Loop(label, kind, body) => {
                 let previous_in_loop = self.in_loop;
                 self.in_loop = true;
 
-                // If a condition is present, this is a goon(n) loop
-                if let Some(count_expr) = condition {
-                    let count_value = self.evaluate(count_expr)?;
-
-                    if let Value::Literal(Literal::Number(n)) = count_value {
-                        let iterations = n as i64;
-
-                        for _ in 0..iterations {
-                            for stmt in body {
-                                self.execute(stmt)?;
-
-                                if self.should_break {
-                                    self.should_break = false;
-                                    break;
-                                }
-                            }
-
-                            if self.should_break {
-                                self.should_break = false;
-                                break;
-                            }
-                        }
-                    } else {
-                        return Err("Loop condition must evaluate to a number.".to_string());
-                    }
-                } else {
-                    // Infinite loop (goon)
-                    loop {
-                        for stmt in body {
-                            self.execute(stmt)?;
-
-                            if self.should_break {
-                                self.should_break = false;
-                                break;
-                            }
-                        }
-
-                        if self.should_break {
-                            self.should_break = false;
-                            break;
-                        }
-                    }
-                }
+                let label = label.as_ref().map(|label| label.lexeme.as_str());
+                let result = self.execute_loop(label, kind, body);
 
                 self.in_loop = previous_in_loop;
-                Ok(())
+                result
             },
-            Stmt::Break => {
+            Stmt::Break(label) => {
                 if self.in_loop {
                     self.should_break = true;
+                    self.jump_label = label.as_ref().map(|label| label.lexeme.clone());
                     Ok(())
                 } else {
                     Err("'sybau' statement outside of a loop.".to_string())
                 }
             },
+            Stmt::Continue(label) => {
+                if self.in_loop {
+                    self.should_continue = true;
+                    self.jump_label = label.as_ref().map(|label| label.lexeme.clone());
+                    Ok(())
+                } else {
+                    Err("'skibidi' statement outside of a loop.".to_string())
+                }
+            },
+            Stmt::Delete(target) => {
+                if !matches!(target, Expr::Index(..)) {
+                    return Err("Invalid 'ghost' target.".to_string());
+                }
+
+                let mut place = self.resolve_place(target)?;
+                let Some(Step::Index(index_val)) = place.steps.pop() else {
+                    unreachable!("an index target ends in an index step")
+                };
+
+                // A missing container is reported like any other read; the
+                // copy read here is dropped before the edit, so it doesn't
+                // force the elements to be copied
+                self.read_place(&place)?;
+
+                self.update_place(&place, |object_val| match object_val {
+                    Value::Array(elements) => {
+                        let i = self.resolve_index(&index_val, elements.len())?;
+                        Rc::make_mut(elements).remove(i);
+                        Ok(())
+                    },
+                    Value::Map(map) => match Rc::make_mut(map).remove(&index_val)? {
+                        Some(_) => Ok(()),
+                        None => Err(format!("Key '{}' not found in map.", index_val)),
+                    },
+                    other => Err(format!("Cannot 'ghost' an element of {}.", other)),
+                })
+            },
+            Stmt::Return(_keyword, value) => {
+                if !self.in_function {
+                    return Err("'tuah' statement outside of a function.".to_string());
+                }
+
+                let value = if let Some(expr) = value {
+                    self.evaluate(expr)?
+                } else {
+                    Value::Literal(Literal::Nil)
+                };
+
+                self.return_value = Some(value);
+                Ok(())
+            },
             Stmt::Function(name, params, body) => {
                 let function = Function {
                     name: name.lexeme.clone(),
                     params: params.iter().map(|param| param.lexeme.clone()).collect(),
-                    body: Box::new(body.clone()),
+                    body: Rc::clone(body),
+                    closure: Rc::clone(&self.environment),
+                    file: self.file.clone(),
+                    is_initializer: false,
                 };
 
-                self.environment.define(
+                self.environment.borrow_mut().define(
                     name.lexeme.clone(),
                     Value::Function(function),
                 );
 
                 Ok(())
             },
+            Stmt::Match(keyword, subject, arms) => {
+                let value = self.evaluate(subject)?;
+
+                for (pattern, guard, body) in arms {
+                    let mut bindings = Vec::new();
+
+                    if !self.match_pattern(pattern, &value, &mut bindings)? {
+                        continue;
+                    }
+
+                    let scope = self.new_scope();
+                    for (name, bound) in bindings {
+                        scope.borrow_mut().define(name, bound);
+                    }
+
+                    if let Some(guard) = guard {
+                        let previous = std::mem::replace(&mut self.environment, Rc::clone(&scope));
+                        let guard_value = self.evaluate(guard);
+                        self.environment = previous;
+
+                        if !self.is_truthy(&guard_value?) {
+                            continue;
+                        }
+                    }
+
+                    return self.execute_block(std::slice::from_ref(body), scope);
+                }
+
+                Err(format!("No 'vibe' arm matched {} at line {}.", value, keyword.line))
+            },
+            Stmt::Throw(keyword, value) => {
+                let value = self.evaluate(value)?;
+
+                let error = match value {
+                    Value::Map(_) => value,
+                    other => error_map("Error", other.to_string(), keyword.line),
+                };
+
+                // If nothing catches it, the message is what gets reported
+                let message = match &error {
+                    Value::Map(map) => {
+                        let key = Value::Literal(Literal::String("message".to_string()));
+                        match map.get(&key)? {
+                            Some(message) => message.to_string(),
+                            None => error.to_string(),
+                        }
+                    },
+                    other => other.to_string(),
+                };
+
+                self.thrown = Some(error);
+                Err(message)
+            },
+            Stmt::Record(name, fields) => {
+                let record_type = RecordType {
+                    name: name.lexeme.clone(),
+                    fields: fields.iter().map(|field| field.lexeme.clone()).collect(),
+                };
+
+                self.environment.borrow_mut().define(
+                    name.lexeme.clone(),
+                    Value::RecordType(Rc::new(record_type)),
+                );
+
+                Ok(())
+            },
+            Stmt::Class(name, superclass, methods) => {
+                let superclass = match superclass {
+                    Some(parent) => match self.evaluate(parent)? {
+                        Value::Class(class) => Some(class),
+                        other => return Err(format!("A clique can only inherit from a clique, got {}.", other)),
+                    },
+                    None => None,
+                };
+
+                // A subclass's methods find `og` one scope outside of `me`
+                let closure = match &superclass {
+                    Some(parent) => {
+                        let scope = self.new_scope();
+                        scope.borrow_mut().define("og".to_string(), Value::Class(Rc::clone(parent)));
+                        scope
+                    },
+                    None => Rc::clone(&self.environment),
+                };
+
+                let mut class_methods = HashMap::new();
+
+                for method in methods {
+                    if let Stmt::Function(method_name, params, body) = method {
+                        class_methods.insert(method_name.lexeme.clone(), Function {
+                            name: format!("{}.{}", name.lexeme, method_name.lexeme),
+                            params: params.iter().map(|param| param.lexeme.clone()).collect(),
+                            body: Rc::clone(body),
+                            closure: Rc::clone(&closure),
+                            file: self.file.clone(),
+                            is_initializer: method_name.lexeme == "spawn",
+                        });
+                    }
+                }
+
+                let class = Class {
+                    name: name.lexeme.clone(),
+                    superclass,
+                    methods: class_methods,
+                };
+
+                self.environment.borrow_mut().define(name.lexeme.clone(), Value::Class(Rc::new(class)));
+
+                Ok(())
+            },
+            Stmt::Import(path, kind) => {
+                let Some(Literal::String(path_text)) = &path.literal else {
+                    unreachable!("module paths are string tokens")
+                };
+
+                let module = self.load_module(path_text)?;
+
+                match kind {
+                    ImportKind::Namespace(alias) => {
+                        let name = alias.as_ref().map_or(module.name.clone(), |alias| alias.lexeme.clone());
+                        self.environment.borrow_mut().define(name, Value::Module(module));
+                    },
+                    ImportKind::Names(names) => {
+                        for name in names {
+                            let value = module_member(&module, name)?;
+                            self.environment.borrow_mut().define(name.lexeme.clone(), value);
+                        }
+                    },
+                }
+
+                Ok(())
+            },
+            Stmt::Try(body, handler, cleanup) => {
+                let mut result = self.execute_block(body, self.new_scope());
+
+                if let (Err(message), Some((name, handler_body))) = (&result, handler) {
+                    let error = self.caught_error(message.clone());
+                    let scope = self.new_scope();
+
+                    if let Some(name) = name {
+                        scope.borrow_mut().define(name.lexeme.clone(), error);
+                    }
+
+                    result = self.execute_block(handler_body, scope);
+                }
+
+                match cleanup {
+                    Some(cleanup_body) => self.run_cleanup(cleanup_body, result),
+                    None => result,
+                }
+            },
         }
     }
 
+    /// Runs the file at `path` (relative to the current file, `.paijorot`
+    /// optional) in a fresh top-level scope, or hands back the module from the
+    /// first time it was summoned.
+    fn load_module(&mut self, path: &str) -> Result<Module, String> {
+        let mut relative = PathBuf::from(path);
+        if relative.extension().is_none() {
+            relative.set_extension("paijorot");
+        }
+
+        let base = self.file.as_deref().and_then(Path::parent).unwrap_or(Path::new(""));
+        let canonical = fs::canonicalize(base.join(&relative))
+            .map_err(|e| format!("Cannot summon '{}': {}.", path, e))?;
+
+        if let Some(start) = self.importing.iter().position(|file| *file == canonical) {
+            let chain: Vec<String> = self.importing[start..]
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|file| file.display().to_string())
+                .collect();
+            return Err(format!("Circular summon: {}.", chain.join(" -> ")));
+        }
+
+        if let Some(module) = self.modules.get(&canonical) {
+            return Ok(module.clone());
+        }
+
+        let source = fs::read_to_string(&canonical)
+            .map_err(|e| format!("Cannot summon '{}': {}.", path, e))?;
+        let statements = Parser::new(Lexer::new(source).scan_tokens()?).parse()?;
+
+        let module = Module {
+            name: canonical.file_stem().map_or(String::new(), |stem| stem.to_string_lossy().into_owned()),
+            environment: native::global_environment(),
+        };
+
+        // The module runs as its own script, so nothing about the summoning
+        // code's position carries over
+        let previous_environment = std::mem::replace(&mut self.environment, Rc::clone(&module.environment));
+        let previous_file = self.file.replace(Rc::from(canonical.as_path()));
+        let previous_in_loop = std::mem::replace(&mut self.in_loop, false);
+        let previous_in_function = std::mem::replace(&mut self.in_function, false);
+        self.importing.push(canonical.clone());
+
+        let result = self.interpret(statements);
+
+        self.importing.pop();
+        self.environment = previous_environment;
+        self.file = previous_file;
+        self.in_loop = previous_in_loop;
+        self.in_function = previous_in_function;
+        result?;
+
+        self.modules.insert(canonical, module.clone());
+        Ok(module)
+    }
+
+    /// Turns a failure into the value a `cope` handler receives: whatever
+    /// `crashout` threw, or a `RuntimeError` map for interpreter errors.
+    fn caught_error(&mut self, message: String) -> Value {
+        match self.thrown.take() {
+            Some(thrown) => thrown,
+            None => error_map("RuntimeError", message, self.line),
+        }
+    }
+
+    /// Runs an `anyway` block on the way out of a `bet`. Whatever was leaving
+    /// the `bet` (an error, `tuah`, `sybau` or `skibidi`) is held back while
+    /// the block runs and resumed afterwards, unless the block itself errors
+    /// or jumps, in which case that wins.
+    fn run_cleanup(&mut self, cleanup: &[Stmt], outcome: Result<(), String>) -> Result<(), String> {
+        let thrown = self.thrown.take();
+        let should_break = std::mem::take(&mut self.should_break);
+        let should_continue = std::mem::take(&mut self.should_continue);
+        let jump_label = self.jump_label.take();
+        let return_value = self.return_value.take();
+
+        self.execute_block(cleanup, self.new_scope())?;
+
+        if self.should_break || self.should_continue || self.return_value.is_some() {
+            return Ok(());
+        }
+
+        self.thrown = thrown;
+        self.should_break = should_break;
+        self.should_continue = should_continue;
+        self.jump_label = jump_label;
+        self.return_value = return_value;
+
+        outcome
+    }
+
+    /// Checks `value` against `pattern`, collecting the names it binds.
+    fn match_pattern(&mut self, pattern: &Pattern, value: &Value, bindings: &mut Vec<(String, Value)>) -> Result<bool, String> {
+        match pattern {
+            Pattern::Wildcard => Ok(true),
+            Pattern::Binding(name) => {
+                bindings.push((name.lexeme.clone(), value.clone()));
+                Ok(true)
+            },
+            Pattern::Literal(expected) => {
+                let expected = self.evaluate(expected)?;
+                Ok(matches!(self.equal(&expected, value)?, Value::Literal(Literal::Boolean(true))))
+            },
+            Pattern::Range(start, _operator, end) => {
+                let start = self.evaluate(start)?;
+                let end = self.evaluate(end)?;
+
+                if as_floats(&start, &end).is_none() {
+                    return Err("Range pattern bounds must be numbers.".to_string());
+                }
+
+                // Anything that isn't a number simply falls outside the range
+                if as_floats(value, &start).is_none() {
+                    return Ok(false);
+                }
+
+                Ok(matches!(self.compare(value, &start)?, Some(Ordering::Greater | Ordering::Equal))
+                    && self.compare(value, &end)? == Some(Ordering::Less))
+            },
+            Pattern::Array(elements) => {
+                let Value::Array(items) = value else {
+                    return Ok(false);
+                };
+
+                if items.len() != elements.len() {
+                    return Ok(false);
+                }
+
+                for (element, item) in elements.iter().zip(items.iter()) {
+                    if !self.match_pattern(element, item, bindings)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
+            },
+        }
+    }
+
+    fn execute_loop(&mut self, label: Option<&str>, kind: &LoopKind, body: &[Stmt]) -> Result<(), String> {
+        match kind {
+            LoopKind::Infinite => loop {
+                if self.run_iteration(label, body, self.new_scope())? {
+                    break;
+                }
+            },
+            LoopKind::Count(count_expr) => {
+                let count_value = self.evaluate(count_expr)?;
+
+                if let Value::Literal(Literal::Integer(iterations)) = count_value {
+                    for _ in 0..iterations {
+                        if self.run_iteration(label, body, self.new_scope())? {
+                            break;
+                        }
+                    }
+                } else {
+                    return Err(
+                        "Loop count must evaluate to an integer; use 'goon yo <condition>' to loop while a condition holds."
+                            .to_string(),
+                    );
+                }
+            },
+            LoopKind::While(condition) => loop {
+                let condition_value = self.evaluate(condition)?;
+
+                if !self.is_truthy(&condition_value) {
+                    break;
+                }
+
+                if self.run_iteration(label, body, self.new_scope())? {
+                    break;
+                }
+            },
+            // Ranges are counted through directly rather than built as an
+            // array first, so huge ranges cost nothing up front
+            LoopKind::Each(name, Expr::Range(start, _operator, end)) => {
+                let (start, end) = self.range_bounds(start, end)?;
+
+                for n in start..end {
+                    if self.run_each_iteration(label, name, Value::Literal(Literal::Integer(n)), body)? {
+                        break;
+                    }
+                }
+            },
+            LoopKind::Each(name, iterable) => {
+                let iterable_value = self.evaluate(iterable)?;
+
+                for item in self.iteration_items(iterable_value)? {
+                    if self.run_each_iteration(label, name, item, body)? {
+                        break;
+                    }
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Runs one pass over a loop body and reports whether the loop should stop.
+    fn run_iteration(&mut self, label: Option<&str>, body: &[Stmt], environment: Rc<RefCell<Environment>>) -> Result<bool, String> {
+        self.execute_block(body, environment)?;
+
+        // A labelled jump aimed at an outer loop stops this one and keeps
+        // unwinding until it reaches the loop with that label
+        if self.jump_label.is_some() && self.jump_label.as_deref() != label {
+            return Ok(true);
+        }
+
+        self.jump_label = None;
+
+        // `skibidi` only cuts the current pass short
+        self.should_continue = false;
+
+        if self.should_break {
+            self.should_break = false;
+            return Ok(true);
+        }
+
+        Ok(self.return_value.is_some())
+    }
+
+    /// Runs one pass of a `goon x in ...` loop with `x` bound to `item`.
+    fn run_each_iteration(&mut self, label: Option<&str>, name: &Token, item: Value, body: &[Stmt]) -> Result<bool, String> {
+        let scope = self.new_scope();
+        scope.borrow_mut().define(name.lexeme.clone(), item);
+
+        self.run_iteration(label, body, scope)
+    }
+
+    fn range_bounds(&mut self, start: &Expr, end: &Expr) -> Result<(i64, i64), String> {
+        match (self.evaluate(start)?, self.evaluate(end)?) {
+            (Value::Literal(Literal::Integer(a)), Value::Literal(Literal::Integer(b))) => Ok((a, b)),
+            _ => Err("Range bounds must be integers.".to_string()),
+        }
+    }
+
+    /// Lists the values a `goon x in ...` loop visits: array elements, string
+    /// characters or map keys.
+    fn iteration_items(&self, iterable: Value) -> Result<Vec<Value>, String> {
+        match iterable {
+            Value::Array(elements) => Ok(Rc::unwrap_or_clone(elements)),
+            Value::Map(map) => Ok(map.keys().cloned().collect()),
+            Value::Literal(Literal::String(s)) => Ok(s
+                .chars()
+                .map(|c| Value::Literal(Literal::String(c.to_string())))
+                .collect()),
+            other => Err(format!("Cannot loop over {}.", other)),
+        }
+    }
+
+    /// Executes statements in order, stopping early once a `sybau`, `skibidi`
+    /// or `tuah` has been hit so the enclosing loop or function can unwind.
+    fn execute_body(&mut self, statements: &[Stmt]) -> Result<(), String> {
+        for stmt in statements {
+            self.execute(stmt)?;
+
+            if self.should_break || self.should_continue || self.return_value.is_some() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs `statements` inside `environment`, restoring the current scope
+    /// afterwards even if execution fails.
+    fn execute_block(&mut self, statements: &[Stmt], environment: Rc<RefCell<Environment>>) -> Result<(), String> {
+        let previous = std::mem::replace(&mut self.environment, environment);
+        let result = self.execute_body(statements);
+        self.environment = previous;
+        result
+    }
+
+    fn new_scope(&self) -> Rc<RefCell<Environment>> {
+        Rc::new(RefCell::new(Environment::new_enclosed(Rc::clone(&self.environment))))
+    }
+
     fn evaluate(&mut self, expr: &Expr) -> Result<Value, String> {
+        if let Expr::Binary(_, token, _)
+        | Expr::Logical(_, token, _)
+        | Expr::Unary(token, _)
+        | Expr::Variable(token)
+        | Expr::Call(_, token, _)
+        | Expr::Index(_, token, _)
+        | Expr::Range(_, token, _)
+        | Expr::Get(_, token)
+        | Expr::Super(token, _)
+        | Expr::CompoundAssign(_, token, _)
+        | Expr::Postfix(_, token) = expr
+        {
+            self.line = token.line;
+        }
+
         match expr {
             Expr::Literal(literal) => {
                 // Special case for yeet (input)
-                if let Literal::String(s) = literal {
-                    if s == "__YEET__" {
-                        return self.handle_input();
-                    }
+                if let Literal::String(s) = literal && s == "__YEET__" {
+                    return self.handle_input();
                 }
 
                 Ok(Value::Literal(literal.clone()))
             },
             Expr::Grouping(expr) => self.evaluate(expr),
             Expr::Variable(name) => {
-                match self.environment.get(&name.lexeme) {
+                match self.environment.borrow().get(&name.lexeme) {
                     Some(value) => Ok(value),
                     None => Err(format!("Undefined variable '{}'.", name.lexeme)),
                 }
             },
             Expr::Binary(left, operator, right) => {
+                // Assignment only evaluates its target as a place, not a value
+                if operator.token_type == TokenType::Pmo {
+                    let value = self.evaluate(right)?;
+                    self.assign(left, value.clone())?;
+                    return Ok(value);
+                }
+
                 let left_val = self.evaluate(left)?;
                 let right_val = self.evaluate(right)?;
 
+                self.binary(operator, &left_val, &right_val)
+            },
+            Expr::CompoundAssign(target, operator, value) => {
+                // The target's indices run once; the same place is then read
+                // and written
+                let place = self.resolve_place(target)?;
+                let current = self.read_place(&place)?;
+                let value = self.evaluate(value)?;
+
+                let updated = self.binary(operator, &current, &value)?;
+                self.write_place(&place, updated.clone())?;
+
+                Ok(updated)
+            },
+            Expr::Postfix(target, operator) => {
+                let place = self.resolve_place(target)?;
+                let current = self.read_place(&place)?;
+
+                let updated = self.binary(operator, &current, &Value::Literal(Literal::Integer(1)))?;
+                self.write_place(&place, updated)?;
+
+                Ok(current)
+            },
+            Expr::Logical(left, operator, right) => {
+                let left_val = self.evaluate(left)?;
+
+                // Short-circuit: `ong` stops at the first truthy operand,
+                // `fr` at the first falsy one
+                if operator.token_type == TokenType::Ong {
+                    if self.is_truthy(&left_val) {
+                        return Ok(left_val);
+                    }
+                } else if !self.is_truthy(&left_val) {
+                    return Ok(left_val);
+                }
+
+                self.evaluate(right)
+            },
+            Expr::Unary(operator, right) => {
+                let right_val = self.evaluate(right)?;
+
                 match operator.token_type {
-                    TokenType::Plus => self.add(&left_val, &right_val),
-                    TokenType::Minus => self.subtract(&left_val, &right_val),
-                    TokenType::Star => self.multiply(&left_val, &right_val),
-                    TokenType::Slash => self.divide(&left_val, &right_val),
-                    TokenType::Modulo => self.modulo(&left_val, &right_val),
-                    TokenType::Greater => self.greater(&left_val, &right_val),
-                    TokenType::GreaterEqual => self.greater_equal(&left_val, &right_val),
-                    TokenType::Less => self.less(&left_val, &right_val),
-                    TokenType::LessEqual => self.less_equal(&left_val, &right_val),
-                    TokenType::Equal => self.equal(&left_val, &right_val),
-                    TokenType::NotEqual => self.not_equal(&left_val, &right_val),
-                    TokenType::Pmo => {
-                        // Handle assignment
-                        if let Expr::Variable(var_name) = &**left {
-                            self.environment.assign(&var_name.lexeme, right_val.clone())?;
-                            Ok(right_val)
-                        } else {
-                            Err("Invalid assignment target.".to_string())
-                        }
+                    TokenType::Nah => Ok(Value::Literal(Literal::Boolean(!self.is_truthy(&right_val)))),
+                    TokenType::Tilde => match right_val {
+                        Value::Literal(Literal::Integer(n)) => Ok(Value::Literal(Literal::Integer(!n))),
+                        _ => Err("Operand must be a 64-bit integer.".to_string()),
                     },
-                    _ => Err(format!("Unsupported binary operation: {:?}", operator.token_type)),
+                    _ => Err(format!("Unsupported unary operation: {:?}", operator.token_type)),
                 }
             },
-            Expr::Array(name, elements) => {
+            Expr::Array(elements) => {
                 let mut array_values = Vec::new();
 
                 for element in elements {
                     array_values.push(self.evaluate(element)?);
                 }
 
-                let array_value = Value::Array(array_values);
-                self.environment.define(name.lexeme.clone(), array_value.clone());
+                Ok(Value::Array(Rc::new(array_values)))
+            },
+            Expr::Map(entries) => {
+                let mut map = Map::new();
 
-                Ok(array_value)
+                for (key, value) in entries {
+                    let key_val = self.evaluate(key)?;
+                    let value_val = self.evaluate(value)?;
+
+                    // Later duplicates overwrite earlier ones
+                    map.insert(key_val, value_val)?;
+                }
+
+                Ok(Value::Map(Rc::new(map)))
             },
             Expr::Call(callee, _paren, arguments) => {
                 let callee_val = self.evaluate(callee)?;
@@ -205,8 +786,290 @@ impl<'a> Interpreter<'a> {
                     arg_values.push(self.evaluate(arg)?);
                 }
 
-                self.call_function(&callee_val, arg_values)
+                // Errors should point inside the callee, but once it returns
+                // the caller's line applies again
+                let line = self.line;
+                let result = self.call_function(&callee_val, arg_values)?;
+                self.line = line;
+
+                Ok(result)
+            },
+            Expr::Range(start, _operator, end) => {
+                let (start, end) = self.range_bounds(start, end)?;
+
+                let mut numbers = Vec::new();
+                let len = usize::try_from(end.saturating_sub(start)).unwrap_or(0);
+                numbers
+                    .try_reserve_exact(len)
+                    .map_err(|_| format!("Range {}..{} is too large to store as an array.", start, end))?;
+
+                numbers.extend((start..end).map(|n| Value::Literal(Literal::Integer(n))));
+                Ok(Value::Array(Rc::new(numbers)))
+            },
+            Expr::Interpolation(parts) => {
+                let mut result = String::new();
+
+                for part in parts {
+                    result.push_str(&self.evaluate(part)?.to_string());
+                }
+
+                Ok(Value::Literal(Literal::String(result)))
+            },
+            Expr::Get(object, name) => {
+                let object_val = self.evaluate(object)?;
+                self.field_value(object_val, name)
+            },
+            Expr::Super(keyword, method) => {
+                let parent = self.environment.borrow().get("og");
+                let instance = self.environment.borrow().get("me");
+
+                match (parent, instance) {
+                    (Some(Value::Class(parent)), Some(instance)) => match parent.find_method(&method.lexeme) {
+                        Some(found) => Ok(Value::Function(found.bind(instance))),
+                        None => Err(format!("Parent clique {} has no method '{}'.", parent.name, method.lexeme)),
+                    },
+                    _ => Err(format!("Can't use 'og' outside of a clique method at line {}.", keyword.line)),
+                }
+            },
+            Expr::Lambda(keyword, params, body) => Ok(Value::Function(Function {
+                name: format!("lambda@{}", keyword.line),
+                params: params.iter().map(|param| param.lexeme.clone()).collect(),
+                body: Rc::clone(body),
+                closure: Rc::clone(&self.environment),
+                file: self.file.clone(),
+                is_initializer: false,
+            })),
+            Expr::Index(object, _bracket, index) => {
+                let object_val = self.evaluate(object)?;
+                let index_val = self.evaluate(index)?;
+                self.index_value(object_val, &index_val)
+            },
+        }
+    }
+
+    /// Applies a non-assigning binary operator to two evaluated operands.
+    fn binary(&self, operator: &Token, left: &Value, right: &Value) -> Result<Value, String> {
+        match operator.token_type {
+            TokenType::Plus => self.add(left, right),
+            TokenType::Minus => self.subtract(left, right),
+            TokenType::Star => self.multiply(left, right),
+            TokenType::Slash => self.divide(left, right),
+            TokenType::Modulo => self.modulo(left, right),
+            TokenType::StarStar => self.power(left, right),
+            TokenType::TildeSlash => self.floor_divide(left, right),
+            TokenType::Ampersand => self.bitwise(left, right, |a, b| a & b),
+            TokenType::Pipe => self.bitwise(left, right, |a, b| a | b),
+            TokenType::Caret => self.bitwise(left, right, |a, b| a ^ b),
+            TokenType::LessLess => self.shift(left, right, true),
+            TokenType::GreaterGreater => self.shift(left, right, false),
+            TokenType::Greater => self.greater(left, right),
+            TokenType::GreaterEqual => self.greater_equal(left, right),
+            TokenType::Less => self.less(left, right),
+            TokenType::LessEqual => self.less_equal(left, right),
+            TokenType::Equal => self.equal(left, right),
+            TokenType::NotEqual => self.not_equal(left, right),
+            _ => Err(format!("Unsupported binary operation: {:?}", operator.token_type)),
+        }
+    }
+
+    /// Reads `object[index]`.
+    fn index_value(&self, object: Value, index: &Value) -> Result<Value, String> {
+        match object {
+            Value::Array(elements) => {
+                let i = self.resolve_index(index, elements.len())?;
+                Ok(elements[i].clone())
+            },
+            Value::Literal(Literal::String(s)) => {
+                let chars: Vec<char> = s.chars().collect();
+                let i = self.resolve_index(index, chars.len())?;
+                Ok(Value::Literal(Literal::String(chars[i].to_string())))
+            },
+            Value::Map(map) => match map.get(index)? {
+                Some(value) => Ok(value.clone()),
+                None => Err(format!("Key '{}' not found in map.", index)),
+            },
+            other => Err(format!("Cannot index into {}.", other)),
+        }
+    }
+
+    /// Reads `object.name`: a module member, a record field, or an
+    /// instance's field or bound method.
+    fn field_value(&self, object: Value, name: &Token) -> Result<Value, String> {
+        match object {
+            Value::Module(module) => module_member(&module, name),
+            Value::Record(mut record) => {
+                let i = record.field_index(&name.lexeme)?;
+                Ok(record.values.swap_remove(i))
+            },
+            Value::Instance(instance) => {
+                if let Some(value) = instance.borrow().fields.get(&name.lexeme) {
+                    return Ok(value.clone());
+                }
+
+                let class = Rc::clone(&instance.borrow().class);
+
+                match class.find_method(&name.lexeme) {
+                    Some(method) => Ok(Value::Function(method.bind(Value::Instance(instance)))),
+                    None => Err(format!("{} instance has no field or method '{}'.", class.name, name.lexeme)),
+                }
             },
+            other => Err(format!("Cannot read field '{}' of {}; only squads and cliques have fields.", name.lexeme, other)),
+        }
+    }
+
+    /// Stores `value` into an assignment target.
+    fn assign(&mut self, target: &Expr, value: Value) -> Result<(), String> {
+        let place = self.resolve_place(target)?;
+        self.write_place(&place, value)
+    }
+
+    /// Evaluates the indices along an assignment target, each exactly once,
+    /// so the target can then be read and written without re-running them.
+    fn resolve_place(&mut self, target: &Expr) -> Result<Place, String> {
+        match target {
+            Expr::Variable(name) => Ok(Place { root: PlaceRoot::Variable(name.clone()), steps: Vec::new() }),
+            Expr::Index(object, _bracket, index) => {
+                let mut place = self.resolve_place(object)?;
+                place.steps.push(Step::Index(self.evaluate(index)?));
+                Ok(place)
+            },
+            Expr::Get(object, name) => {
+                let mut place = self.resolve_place(object)?;
+                place.steps.push(Step::Field(name.clone()));
+                Ok(place)
+            },
+            other => match self.evaluate(other)? {
+                // Writing into a temporary only sticks when it is shared
+                instance @ Value::Instance(_) => Ok(Place { root: PlaceRoot::Instance(instance), steps: Vec::new() }),
+                _ => Err("Invalid assignment target.".to_string()),
+            },
+        }
+    }
+
+    fn read_place(&self, place: &Place) -> Result<Value, String> {
+        let mut value = self.place_root(place)?;
+
+        for step in &place.steps {
+            value = match step {
+                Step::Index(index) => self.index_value(value, index)?,
+                Step::Field(name) => self.field_value(value, name)?,
+            };
+        }
+
+        Ok(value)
+    }
+
+    /// Stores `value` at `place`.
+    fn write_place(&self, place: &Place, value: Value) -> Result<(), String> {
+        self.update_place(place, |slot| {
+            *slot = value;
+            Ok(())
+        })
+    }
+
+    /// Runs `update` on the value at `place`. The containers along the way
+    /// are edited where they are stored rather than copied and written back,
+    /// so only an array that is still shared with another value gets copied.
+    fn update_place(&self, place: &Place, update: impl FnOnce(&mut Value) -> Result<(), String>) -> Result<(), String> {
+        match &place.root {
+            PlaceRoot::Variable(name) => self.environment
+                .borrow_mut()
+                .update(&name.lexeme, |root| self.update_steps(root, &place.steps, update)),
+            PlaceRoot::Instance(instance) => self.update_steps(&mut instance.clone(), &place.steps, update),
+        }
+    }
+
+    fn place_root(&self, place: &Place) -> Result<Value, String> {
+        match &place.root {
+            PlaceRoot::Variable(name) => match self.environment.borrow().get(&name.lexeme) {
+                Some(value) => Ok(value),
+                None => Err(format!("Undefined variable '{}'.", name.lexeme)),
+            },
+            PlaceRoot::Instance(instance) => Ok(instance.clone()),
+        }
+    }
+
+    /// Follows `steps` down from `container` and runs `update` on the value
+    /// they lead to. A missing map key or instance field at the end starts
+    /// out as `nil` and is only added if `update` succeeds.
+    fn update_steps(&self, container: &mut Value, steps: &[Step], update: impl FnOnce(&mut Value) -> Result<(), String>) -> Result<(), String> {
+        let Some((step, rest)) = steps.split_first() else {
+            return update(container);
+        };
+
+        match (container, step) {
+            (Value::Array(elements), Step::Index(index)) => {
+                let i = self.resolve_index(index, elements.len())?;
+                self.update_steps(&mut Rc::make_mut(elements)[i], rest, update)
+            },
+            (Value::Map(map), Step::Index(key)) => {
+                let map = Rc::make_mut(map);
+
+                match map.get_mut(key)? {
+                    Some(value) => self.update_steps(value, rest, update),
+                    None if rest.is_empty() => {
+                        let mut value = Value::Literal(Literal::Nil);
+                        update(&mut value)?;
+                        map.insert(key.clone(), value)
+                    },
+                    None => Err(format!("Key '{}' not found in map.", key)),
+                }
+            },
+            (Value::Record(record), Step::Field(name)) => {
+                let i = record.field_index(&name.lexeme)?;
+                self.update_steps(&mut record.values[i], rest, update)
+            },
+            (Value::Instance(instance), Step::Field(name)) => {
+                let field = instance.borrow().fields.get(&name.lexeme).cloned();
+
+                match field {
+                    // Shared already, so it can be edited through this copy
+                    Some(mut shared @ Value::Instance(_)) if !rest.is_empty() => {
+                        self.update_steps(&mut shared, rest, update)
+                    },
+                    // Taken out while the rest of the path runs, since that
+                    // path may lead back into this same instance
+                    Some(_) => {
+                        let mut field = instance.borrow_mut().fields.remove(&name.lexeme)
+                            .expect("the field was just read");
+                        let result = self.update_steps(&mut field, rest, update);
+                        instance.borrow_mut().fields.insert(name.lexeme.clone(), field);
+                        result
+                    },
+                    None if rest.is_empty() => {
+                        let mut value = Value::Literal(Literal::Nil);
+                        update(&mut value)?;
+                        instance.borrow_mut().fields.insert(name.lexeme.clone(), value);
+                        Ok(())
+                    },
+                    None => {
+                        let mut method = self.field_value(Value::Instance(Rc::clone(instance)), name)?;
+                        self.update_steps(&mut method, rest, update)
+                    },
+                }
+            },
+            (other, Step::Index(_)) => Err(format!("Cannot assign to an index of {}.", other)),
+            (other, Step::Field(name)) => {
+                Err(format!("Cannot set field '{}' of {}; only squads and cliques have fields.", name.lexeme, other))
+            },
+        }
+    }
+
+    /// Turns an index value into a position within a sequence of `len`
+    /// items. Negative indices count back from the end.
+    fn resolve_index(&self, index: &Value, len: usize) -> Result<usize, String> {
+        match index {
+            Value::Literal(Literal::Integer(n)) => {
+                let position = if *n < 0 { *n + len as i64 } else { *n };
+
+                if position < 0 || position >= len as i64 {
+                    Err(format!("Index {} out of bounds for length {}.", n, len))
+                } else {
+                    Ok(position as usize)
+                }
+            },
+            Value::Literal(Literal::BigInt(n)) => Err(format!("Index {} out of bounds for length {}.", n, len)),
+            other => Err(format!("Index must be an integer, got {}.", other)),
         }
     }
 
@@ -220,10 +1083,17 @@ impl<'a> Interpreter<'a> {
         let mut input = String::new();
         match stdin.lock().read_line(&mut input) {
             Ok(_) => {
-                // Try to parse as number first
-                match input.trim().parse::<f64>() {
-                    Ok(n) => Ok(Value::Literal(Literal::Number(n))),
-                    Err(_) => Ok(Value::Literal(Literal::String(input.trim().to_string()))),
+                // Try to parse as an integer, then a float, then fall back to text
+                let input = input.trim();
+
+                if let Ok(n) = input.parse::<i64>() {
+                    Ok(Value::Literal(Literal::Integer(n)))
+                } else if let Some(n) = BigInt::parse(input) {
+                    Ok(Value::Literal(Literal::BigInt(n)))
+                } else if let Ok(n) = input.parse::<f64>() {
+                    Ok(Value::Literal(Literal::Float(n)))
+                } else {
+                    Ok(Value::Literal(Literal::String(input.to_string())))
                 }
             },
             Err(_) => Err("Failed to read input.".to_string()),
@@ -231,7 +1101,17 @@ impl<'a> Interpreter<'a> {
     }
 
     fn call_function(&mut self, callee: &Value, arguments: Vec<Value>) -> Result<Value, String> {
-        if let Value::Function(function) = callee {
+        if let Value::NativeFunction(native) = callee {
+            if native.arity != arguments.len() {
+                return Err(format!(
+                    "Expected {} arguments but got {}.",
+                    native.arity,
+                    arguments.len()
+                ));
+            }
+
+            (native.function)(self, arguments)
+        } else if let Value::Function(function) = callee {
             if function.params.len() != arguments.len() {
                 return Err(format!(
                     "Expected {} arguments but got {}.",
@@ -240,25 +1120,69 @@ impl<'a> Interpreter<'a> {
                 ));
             }
 
-            // Store arguments in a temporary environment
-            let mut temp_env = Environment::new();
+            // Each call gets its own scope holding the arguments; free names
+            // resolve against the scope the function was defined in
+            let mut environment = Environment::new_enclosed(Rc::clone(&function.closure));
 
-            // Define arguments in the temporary environment
             for (param, arg) in function.params.iter().zip(arguments) {
-                temp_env.define(param.clone(), arg);
+                environment.define(param.clone(), arg);
             }
 
-            // Create a new interpreter with the temporary environment
-            let mut interpreter = Interpreter {
-                environment: &mut temp_env,
-                in_loop: false,
-                should_break: false,
-            };
+            if self.call_depth >= MAX_CALL_DEPTH {
+                return Err("Stack overflow.".to_string());
+            }
 
-            // Evaluate the function body
-            let result = interpreter.evaluate(&function.body)?;
+            let previous_in_loop = std::mem::replace(&mut self.in_loop, false);
+            let previous_in_function = std::mem::replace(&mut self.in_function, true);
+            let previous_file = std::mem::replace(&mut self.file, function.file.clone());
+            self.call_depth += 1;
+
+            // Run the function body; falling off the end returns nil
+            let result = self.execute_block(&function.body, Rc::new(RefCell::new(environment)));
+
+            self.call_depth -= 1;
+            self.in_loop = previous_in_loop;
+            self.in_function = previous_in_function;
+            self.file = previous_file;
+            result?;
+
+            let value = self.return_value.take();
+
+            if function.is_initializer {
+                return Ok(function.closure.borrow().get("me").unwrap_or(Value::Literal(Literal::Nil)));
+            }
+
+            Ok(value.unwrap_or(Value::Literal(Literal::Nil)))
+        } else if let Value::Class(class) = callee {
+            let instance = Value::Instance(Rc::new(RefCell::new(Instance {
+                class: Rc::clone(class),
+                fields: HashMap::new(),
+            })));
+
+            match class.find_method("spawn") {
+                Some(initializer) => {
+                    self.call_function(&Value::Function(initializer.bind(instance.clone())), arguments)?;
+                },
+                None if !arguments.is_empty() => {
+                    return Err(format!("Expected 0 arguments but got {}.", arguments.len()));
+                },
+                None => {},
+            }
+
+            Ok(instance)
+        } else if let Value::RecordType(record_type) = callee {
+            if record_type.fields.len() != arguments.len() {
+                return Err(format!(
+                    "Expected {} arguments but got {}.",
+                    record_type.fields.len(),
+                    arguments.len()
+                ));
+            }
 
-            Ok(result)
+            Ok(Value::Record(Record {
+                record_type: Rc::clone(record_type),
+                values: arguments,
+            }))
         } else {
             Err("Can only call functions.".to_string())
         }
@@ -273,108 +1197,223 @@ impl<'a> Interpreter<'a> {
     }
 
     fn add(&self, left: &Value, right: &Value) -> Result<Value, String> {
+        if let Some(result) = integer_arithmetic(left, right, i64::checked_add, |a, b| a + b) {
+            return Ok(result);
+        }
+
         match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                Ok(Value::Literal(Literal::Number(a + b)))
-            },
             (Value::Literal(Literal::String(a)), Value::Literal(Literal::String(b))) => {
                 Ok(Value::Literal(Literal::String(format!("{}{}", a, b))))
             },
             (Value::Literal(Literal::String(a)), b) => {
-                Ok(Value::Literal(Literal::String(format!("{}{}", a, b.to_string()))))
+                Ok(Value::Literal(Literal::String(format!("{}{}", a, b))))
             },
             (a, Value::Literal(Literal::String(b))) => {
-                Ok(Value::Literal(Literal::String(format!("{}{}", a.to_string(), b))))
+                Ok(Value::Literal(Literal::String(format!("{}{}", a, b))))
+            },
+            _ => match as_floats(left, right) {
+                Some((a, b)) => Ok(Value::Literal(Literal::Float(a + b))),
+                None => Err("Operands must be numbers or strings.".to_string()),
             },
-            _ => Err("Operands must be numbers or strings.".to_string()),
         }
     }
 
     fn subtract(&self, left: &Value, right: &Value) -> Result<Value, String> {
-        match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                Ok(Value::Literal(Literal::Number(a - b)))
-            },
-            _ => Err("Operands must be numbers.".to_string()),
+        if let Some(result) = integer_arithmetic(left, right, i64::checked_sub, |a, b| a - b) {
+            return Ok(result);
+        }
+
+        match as_floats(left, right) {
+            Some((a, b)) => Ok(Value::Literal(Literal::Float(a - b))),
+            None => Err("Operands must be numbers.".to_string()),
         }
     }
 
     fn multiply(&self, left: &Value, right: &Value) -> Result<Value, String> {
-        match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                Ok(Value::Literal(Literal::Number(a * b)))
-            },
-            _ => Err("Operands must be numbers.".to_string()),
+        if let Some(result) = integer_arithmetic(left, right, i64::checked_mul, |a, b| a * b) {
+            return Ok(result);
+        }
+
+        match as_floats(left, right) {
+            Some((a, b)) => Ok(Value::Literal(Literal::Float(a * b))),
+            None => Err("Operands must be numbers.".to_string()),
         }
     }
 
+    /// Integer division truncates toward zero; any float operand gives a
+    /// float result.
     fn divide(&self, left: &Value, right: &Value) -> Result<Value, String> {
-        match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                if *b == 0.0 {
-                    Err("Division by zero.".to_string())
-                } else {
-                    Ok(Value::Literal(Literal::Number(a / b)))
-                }
-            },
-            _ => Err("Operands must be numbers.".to_string()),
+        if let Value::Literal(Literal::Integer(0)) = right {
+            return Err("Division by zero.".to_string());
+        }
+
+        if let Some(result) = integer_arithmetic(left, right, i64::checked_div, |a, b| a.div_rem(b).0) {
+            return Ok(result);
+        }
+
+        match as_floats(left, right) {
+            Some((_, 0.0)) => Err("Division by zero.".to_string()),
+            Some((a, b)) => Ok(Value::Literal(Literal::Float(a / b))),
+            None => Err("Operands must be numbers.".to_string()),
         }
     }
 
     fn modulo(&self, left: &Value, right: &Value) -> Result<Value, String> {
-        match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                if *b == 0.0 {
-                    Err("Modulo by zero.".to_string())
-                } else {
-                    Ok(Value::Literal(Literal::Number(a % b)))
-                }
-            },
-            _ => Err("Operands must be numbers.".to_string()),
+        if let Value::Literal(Literal::Integer(0)) = right {
+            return Err("Modulo by zero.".to_string());
+        }
+
+        if let Some(result) = integer_arithmetic(left, right, i64::checked_rem, |a, b| a.div_rem(b).1) {
+            return Ok(result);
+        }
+
+        match as_floats(left, right) {
+            Some((_, 0.0)) => Err("Modulo by zero.".to_string()),
+            Some((a, b)) => Ok(Value::Literal(Literal::Float(a % b))),
+            None => Err("Operands must be numbers.".to_string()),
         }
     }
 
-    fn greater(&self, left: &Value, right: &Value) -> Result<Value, String> {
-        match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                Ok(Value::Literal(Literal::Boolean(a > b)))
-            },
-            _ => Err("Operands must be numbers.".to_string()),
+    /// Integer powers with a non-negative exponent stay exact; anything else
+    /// is computed with floats.
+    fn power(&self, left: &Value, right: &Value) -> Result<Value, String> {
+        if let (Some(base), Value::Literal(Literal::Integer(exponent))) = (as_big(left), right)
+            && *exponent >= 0
+        {
+            // The result has about log2(|base|) * exponent bits; 0, 1 and -1
+            // come out at or below zero and stay small
+            if base.to_f64().abs().log2() * *exponent as f64 > MAX_RESULT_BITS as f64 {
+                return Err("Exponent is too large.".to_string());
+            }
+
+            return Ok(integer_value(base.pow(*exponent as u64)));
+        }
+
+        if let Value::Literal(Literal::BigInt(_)) = right
+            && as_big(left).is_some()
+        {
+            return Err("Exponent is too large.".to_string());
+        }
+
+        match as_floats(left, right) {
+            Some((a, b)) => Ok(Value::Literal(Literal::Float(a.powf(b)))),
+            None => Err("Operands must be numbers.".to_string()),
         }
     }
 
-    fn greater_equal(&self, left: &Value, right: &Value) -> Result<Value, String> {
-        match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                Ok(Value::Literal(Literal::Boolean(a >= b)))
-            },
-            _ => Err("Operands must be numbers.".to_string()),
+    /// Division rounding toward negative infinity. Integers give an integer
+    /// and floats give a whole-valued float.
+    fn floor_divide(&self, left: &Value, right: &Value) -> Result<Value, String> {
+        if let Value::Literal(Literal::Integer(0)) = right {
+            return Err("Division by zero.".to_string());
+        }
+
+        if let Some(result) = integer_arithmetic(left, right, floor_div_i64, |a, b| a.div_floor(b)) {
+            return Ok(result);
+        }
+
+        match as_floats(left, right) {
+            Some((_, 0.0)) => Err("Division by zero.".to_string()),
+            Some((a, b)) => Ok(Value::Literal(Literal::Float((a / b).floor()))),
+            None => Err("Operands must be numbers.".to_string()),
         }
     }
 
-    fn less(&self, left: &Value, right: &Value) -> Result<Value, String> {
+    fn bitwise(&self, left: &Value, right: &Value, op: fn(i64, i64) -> i64) -> Result<Value, String> {
         match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                Ok(Value::Literal(Literal::Boolean(a < b)))
+            (Value::Literal(Literal::Integer(a)), Value::Literal(Literal::Integer(b))) => {
+                Ok(Value::Literal(Literal::Integer(op(*a, *b))))
             },
-            _ => Err("Operands must be numbers.".to_string()),
+            _ => Err("Operands must be 64-bit integers.".to_string()),
         }
     }
 
-    fn less_equal(&self, left: &Value, right: &Value) -> Result<Value, String> {
-        match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                Ok(Value::Literal(Literal::Boolean(a <= b)))
-            },
-            _ => Err("Operands must be numbers.".to_string()),
+    /// Shifts act like multiplying or floor-dividing by a power of two, so
+    /// left shifts never overflow.
+    fn shift(&self, left: &Value, right: &Value, to_left: bool) -> Result<Value, String> {
+        let (Some(value), Value::Literal(Literal::Integer(amount))) = (as_big(left), right) else {
+            return Err("Operands must be integers.".to_string());
+        };
+
+        if *amount < 0 {
+            return Err("Shift amount must not be negative.".to_string());
+        }
+
+        if let Value::Literal(Literal::Integer(n)) = left {
+            if !to_left {
+                // `>>` floors, so shifting everything out leaves 0 or -1
+                return Ok(Value::Literal(Literal::Integer(n >> (*amount).min(63))));
+            }
+
+            if *amount < 63 && (n << amount) >> amount == *n {
+                return Ok(Value::Literal(Literal::Integer(n << amount)));
+            }
+        }
+
+        if !to_left && *amount as u64 >= value.bits_upper_bound() {
+            let sign = if value < BigInt::from_i64(0) { -1 } else { 0 };
+            return Ok(Value::Literal(Literal::Integer(sign)));
+        }
+
+        if value.is_zero() {
+            return Ok(Value::Literal(Literal::Integer(0)));
+        }
+
+        if to_left && value.bits_upper_bound().saturating_add(*amount as u64) > MAX_RESULT_BITS {
+            return Err("Shift amount is too large.".to_string());
+        }
+
+        let factor = BigInt::from_i64(2).pow(*amount as u64);
+
+        if to_left {
+            Ok(integer_value(&value * &factor))
+        } else {
+            Ok(integer_value(value.div_floor(&factor)))
+        }
+    }
+
+    /// Orders two numbers, comparing integers exactly.
+    fn compare(&self, left: &Value, right: &Value) -> Result<Option<Ordering>, String> {
+        if let (Value::Literal(Literal::Integer(a)), Value::Literal(Literal::Integer(b))) = (left, right) {
+            return Ok(Some(a.cmp(b)));
+        }
+
+        if let (Some(a), Some(b)) = (as_big(left), as_big(right)) {
+            return Ok(Some(a.cmp(&b)));
+        }
+
+        match as_floats(left, right) {
+            Some((a, b)) => Ok(a.partial_cmp(&b)),
+            None => Err("Operands must be numbers.".to_string()),
         }
     }
 
+    fn greater(&self, left: &Value, right: &Value) -> Result<Value, String> {
+        let ordering = self.compare(left, right)?;
+        Ok(Value::Literal(Literal::Boolean(ordering == Some(Ordering::Greater))))
+    }
+
+    fn greater_equal(&self, left: &Value, right: &Value) -> Result<Value, String> {
+        let ordering = self.compare(left, right)?;
+        Ok(Value::Literal(Literal::Boolean(matches!(ordering, Some(Ordering::Greater | Ordering::Equal)))))
+    }
+
+    fn less(&self, left: &Value, right: &Value) -> Result<Value, String> {
+        let ordering = self.compare(left, right)?;
+        Ok(Value::Literal(Literal::Boolean(ordering == Some(Ordering::Less))))
+    }
+
+    fn less_equal(&self, left: &Value, right: &Value) -> Result<Value, String> {
+        let ordering = self.compare(left, right)?;
+        Ok(Value::Literal(Literal::Boolean(matches!(ordering, Some(Ordering::Less | Ordering::Equal)))))
+    }
+
     fn equal(&self, left: &Value, right: &Value) -> Result<Value, String> {
+        if let (Some(a), Some(b)) = (as_big(left), as_big(right)) {
+            return Ok(Value::Literal(Literal::Boolean(a == b)));
+        }
+
         match (left, right) {
-            (Value::Literal(Literal::Number(a)), Value::Literal(Literal::Number(b))) => {
-                Ok(Value::Literal(Literal::Boolean(a == b)))
-            },
             (Value::Literal(Literal::String(a)), Value::Literal(Literal::String(b))) => {
                 Ok(Value::Literal(Literal::Boolean(a == b)))
             },
@@ -384,7 +1423,13 @@ impl<'a> Interpreter<'a> {
             (Value::Literal(Literal::Nil), Value::Literal(Literal::Nil)) => {
                 Ok(Value::Literal(Literal::Boolean(true)))
             },
-            _ => Ok(Value::Literal(Literal::Boolean(false))),
+            (Value::Instance(a), Value::Instance(b)) => {
+                Ok(Value::Literal(Literal::Boolean(Rc::ptr_eq(a, b))))
+            },
+            _ => match as_floats(left, right) {
+                Some((a, b)) => Ok(Value::Literal(Literal::Boolean(a == b))),
+                None => Ok(Value::Literal(Literal::Boolean(false))),
+            },
         }
     }
 
@@ -397,3 +1442,87 @@ impl<'a> Interpreter<'a> {
         }
     }
 }
+
+/// Reads one of a module's top-level definitions.
+fn module_member(module: &Module, name: &Token) -> Result<Value, String> {
+    module.environment
+        .borrow()
+        .get_local(&name.lexeme)
+        .ok_or_else(|| format!("Module '{}' has no '{}'.", module.name, name.lexeme))
+}
+
+/// Builds the `{"type": ..., "message": ..., "line": ...}` map that describes
+/// an error to a `cope` handler.
+fn error_map(kind: &str, message: String, line: usize) -> Value {
+    let string = |s: &str| Value::Literal(Literal::String(s.to_string()));
+
+    let mut map = Map::new();
+    for (key, value) in [
+        ("type", string(kind)),
+        ("message", Value::Literal(Literal::String(message))),
+        ("line", Value::Literal(Literal::Integer(line as i64))),
+    ] {
+        map.insert(string(key), value).expect("string keys are always valid");
+    }
+
+    Value::Map(Rc::new(map))
+}
+
+/// Widens a pair of numeric operands to floats. Returns `None` if either
+/// operand is not a number.
+fn as_floats(left: &Value, right: &Value) -> Option<(f64, f64)> {
+    let widen = |value: &Value| match value {
+        Value::Literal(Literal::Integer(n)) => Some(*n as f64),
+        Value::Literal(Literal::BigInt(n)) => Some(n.to_f64()),
+        Value::Literal(Literal::Float(n)) => Some(*n),
+        _ => None,
+    };
+
+    Some((widen(left)?, widen(right)?))
+}
+
+/// Widens an integer operand to a `BigInt`. Returns `None` for non-integers.
+fn as_big(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Literal(Literal::Integer(n)) => Some(BigInt::from_i64(*n)),
+        Value::Literal(Literal::BigInt(n)) => Some(n.clone()),
+        _ => None,
+    }
+}
+
+fn floor_div_i64(a: i64, b: i64) -> Option<i64> {
+    let quotient = a.checked_div(b)?;
+
+    if a % b != 0 && (a < 0) != (b < 0) {
+        quotient.checked_sub(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Stores an integer in the smallest representation that holds it, so
+/// `Literal::BigInt` only ever appears outside the `i64` range.
+fn integer_value(n: BigInt) -> Value {
+    match n.to_i64() {
+        Some(small) => Value::Literal(Literal::Integer(small)),
+        None => Value::Literal(Literal::BigInt(n)),
+    }
+}
+
+/// Runs an exact integer operation on `i64`s, redoing it with `BigInt`s when
+/// that overflows or an operand is already big. Returns `None` unless both
+/// operands are integers.
+fn integer_arithmetic(
+    left: &Value,
+    right: &Value,
+    small: fn(i64, i64) -> Option<i64>,
+    big: fn(&BigInt, &BigInt) -> BigInt,
+) -> Option<Value> {
+    if let (Value::Literal(Literal::Integer(a)), Value::Literal(Literal::Integer(b))) = (left, right)
+        && let Some(n) = small(*a, *b)
+    {
+        return Some(Value::Literal(Literal::Integer(n)));
+    }
+
+    Some(integer_value(big(&as_big(left)?, &as_big(right)?)))
+}
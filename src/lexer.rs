@@ -1,3 +1,4 @@
+use crate::bigint::BigInt;
 use crate::token::{Token, TokenType, Literal};
 use std::collections::HashMap;
 
@@ -8,6 +9,9 @@ pub struct Lexer {
     current: usize,
     line: usize,
     keywords: HashMap<String, TokenType>,
+    /// One entry per `{` interpolation currently open inside a string,
+    /// counting the unmatched braces within its expression.
+    interpolations: Vec<usize>,
 }
 
 impl Lexer {
@@ -24,8 +28,26 @@ impl Lexer {
         keywords.insert("edge".to_string(), TokenType::Edge);
         keywords.insert("yeet".to_string(), TokenType::Yeet);
         keywords.insert("sybau".to_string(), TokenType::Sybau);
+        keywords.insert("skibidi".to_string(), TokenType::Skibidi);
         keywords.insert("yo".to_string(), TokenType::Yo);
         keywords.insert("gurt".to_string(), TokenType::Gurt);
+        keywords.insert("nocap".to_string(), TokenType::Boolean); // true
+        keywords.insert("cap".to_string(), TokenType::Boolean);   // false
+        keywords.insert("fr".to_string(), TokenType::Fr);
+        keywords.insert("ong".to_string(), TokenType::Ong);
+        keywords.insert("nah".to_string(), TokenType::Nah);
+        keywords.insert("ghost".to_string(), TokenType::Ghost);
+        keywords.insert("in".to_string(), TokenType::In);
+        keywords.insert("vibe".to_string(), TokenType::Vibe);
+        keywords.insert("crashout".to_string(), TokenType::Crashout);
+        keywords.insert("bet".to_string(), TokenType::Bet);
+        keywords.insert("cope".to_string(), TokenType::Cope);
+        keywords.insert("anyway".to_string(), TokenType::Anyway);
+        keywords.insert("summon".to_string(), TokenType::Summon);
+        keywords.insert("squad".to_string(), TokenType::Squad);
+        keywords.insert("clique".to_string(), TokenType::Clique);
+        keywords.insert("me".to_string(), TokenType::Me);
+        keywords.insert("og".to_string(), TokenType::Og);
 
         Lexer {
             source: source.chars().collect(),
@@ -34,6 +56,7 @@ impl Lexer {
             current: 0,
             line: 1,
             keywords,
+            interpolations: Vec::new(),
         }
     }
 
@@ -43,6 +66,10 @@ impl Lexer {
             self.scan_token()?;
         }
 
+        if !self.interpolations.is_empty() {
+            return Err(format!("Unterminated string interpolation at line {}", self.line));
+        }
+
         self.tokens.push(Token::new(
             TokenType::EOF,
             "".to_string(),
@@ -59,27 +86,123 @@ impl Lexer {
         match c {
             '(' => self.add_token(TokenType::LeftParen),
             ')' => self.add_token(TokenType::RightParen),
-            '{' => self.add_token(TokenType::LeftBrace),
-            '}' => self.add_token(TokenType::RightBrace),
+            '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                self.add_token(TokenType::LeftBrace);
+            },
+            '}' => match self.interpolations.last_mut() {
+                // Closes an interpolated expression: resume the string
+                Some(0) => {
+                    self.interpolations.pop();
+                    self.string()?;
+                },
+                Some(depth) => {
+                    *depth -= 1;
+                    self.add_token(TokenType::RightBrace);
+                },
+                None => self.add_token(TokenType::RightBrace),
+            },
+            '[' => self.add_token(TokenType::LeftBracket),
+            ']' => self.add_token(TokenType::RightBracket),
             ',' => self.add_token(TokenType::Comma),
+            ':' => self.add_token(TokenType::Colon),
+            '.' => {
+                if self.match_char('.') {
+                    self.add_token(TokenType::DotDot);
+                } else {
+                    self.add_token(TokenType::Dot);
+                }
+            },
             ';' => self.add_token(TokenType::Semicolon),
-            '+' => self.add_token(TokenType::Plus),
-            '-' => self.add_token(TokenType::Minus),
-            '*' => self.add_token(TokenType::Star),
+            '+' => {
+                if self.match_char('+') {
+                    self.add_token(TokenType::PlusPlus);
+                } else if self.match_pmo() {
+                    self.add_token(TokenType::PlusPmo);
+                } else {
+                    self.add_token(TokenType::Plus);
+                }
+            },
+            '-' => {
+                // `--` is left as two minuses; the parser decides whether it
+                // is a decrement or `a - -b`
+                if self.match_pmo() {
+                    self.add_token(TokenType::MinusPmo);
+                } else {
+                    self.add_token(TokenType::Minus);
+                }
+            },
+            '*' => {
+                if self.match_char('*') {
+                    if self.match_pmo() {
+                        self.add_token(TokenType::StarStarPmo);
+                    } else {
+                        self.add_token(TokenType::StarStar);
+                    }
+                } else if self.match_pmo() {
+                    self.add_token(TokenType::StarPmo);
+                } else {
+                    self.add_token(TokenType::Star);
+                }
+            },
+            '~' => {
+                if self.match_char('/') {
+                    if self.match_pmo() {
+                        self.add_token(TokenType::TildeSlashPmo);
+                    } else {
+                        self.add_token(TokenType::TildeSlash);
+                    }
+                } else {
+                    self.add_token(TokenType::Tilde);
+                }
+            },
+            '&' => {
+                if self.match_pmo() {
+                    self.add_token(TokenType::AmpersandPmo);
+                } else {
+                    self.add_token(TokenType::Ampersand);
+                }
+            },
+            '|' => {
+                if self.match_pmo() {
+                    self.add_token(TokenType::PipePmo);
+                } else {
+                    self.add_token(TokenType::Pipe);
+                }
+            },
+            '^' => {
+                if self.match_pmo() {
+                    self.add_token(TokenType::CaretPmo);
+                } else {
+                    self.add_token(TokenType::Caret);
+                }
+            },
             '/' => {
                 if self.match_char('/') {
                     // Comment goes until the end of the line
                     while self.peek() != '\n' && !self.is_at_end() {
                         self.advance();
                     }
+                } else if self.match_pmo() {
+                    self.add_token(TokenType::SlashPmo);
                 } else {
                     self.add_token(TokenType::Slash);
                 }
             },
-            '%' => self.add_token(TokenType::Modulo),
+            '%' => {
+                if self.match_pmo() {
+                    self.add_token(TokenType::ModuloPmo);
+                } else {
+                    self.add_token(TokenType::Modulo);
+                }
+            },
             '=' => {
                 if self.match_char('=') {
                     self.add_token(TokenType::Equal);
+                } else if self.match_char('>') {
+                    self.add_token(TokenType::FatArrow);
                 } else {
                     return Err(format!("Unexpected character '=' at line {}", self.line));
                 }
@@ -94,6 +217,12 @@ impl Lexer {
             '>' => {
                 if self.match_char('=') {
                     self.add_token(TokenType::GreaterEqual);
+                } else if self.match_char('>') {
+                    if self.match_pmo() {
+                        self.add_token(TokenType::GreaterGreaterPmo);
+                    } else {
+                        self.add_token(TokenType::GreaterGreater);
+                    }
                 } else {
                     self.add_token(TokenType::Greater);
                 }
@@ -101,6 +230,12 @@ impl Lexer {
             '<' => {
                 if self.match_char('=') {
                     self.add_token(TokenType::LessEqual);
+                } else if self.match_char('<') {
+                    if self.match_pmo() {
+                        self.add_token(TokenType::LessLessPmo);
+                    } else {
+                        self.add_token(TokenType::LessLess);
+                    }
                 } else {
                     self.add_token(TokenType::Less);
                 }
@@ -131,7 +266,11 @@ impl Lexer {
 
         let token_type = self.keywords.get(&text).cloned().unwrap_or(TokenType::Identifier);
 
-        self.add_token(token_type);
+        if token_type == TokenType::Boolean {
+            self.add_token_literal(token_type, Some(Literal::Boolean(text == "nocap")));
+        } else {
+            self.add_token(token_type);
+        }
     }
 
     fn number(&mut self) -> Result<(), String> {
@@ -147,22 +286,48 @@ impl Lexer {
             while self.is_digit(self.peek()) {
                 self.advance();
             }
+
+            let value: String = self.source[self.start..self.current].iter().collect();
+            let parsed_value = value.parse::<f64>().map_err(|_| {
+                format!("Failed to parse number at line {}", self.line)
+            })?;
+
+            self.add_token_literal(TokenType::Number, Some(Literal::Float(parsed_value)));
+            return Ok(());
         }
 
         let value: String = self.source[self.start..self.current].iter().collect();
-        let parsed_value = value.parse::<f64>().map_err(|_| {
-            format!("Failed to parse number at line {}", self.line)
-        })?;
+        let literal = match value.parse::<i64>() {
+            Ok(n) => Literal::Integer(n),
+            Err(_) => match BigInt::parse(&value) {
+                Some(n) => Literal::BigInt(n),
+                None => return Err(format!("Failed to parse number at line {}", self.line)),
+            },
+        };
 
-        self.add_token_literal(TokenType::Number, Some(Literal::Number(parsed_value)));
+        self.add_token_literal(TokenType::Number, Some(literal));
         Ok(())
     }
 
+    /// Scans a string segment starting just after its opening `"` or after
+    /// the `}` that closed an interpolation. A segment ending in `{` becomes
+    /// an `Interpolation` token and the expression inside is scanned as
+    /// normal tokens until the matching `}`.
     fn string(&mut self) -> Result<(), String> {
-        while self.peek() != '"' && !self.is_at_end() {
+        while self.peek() != '"' && self.peek() != '{' && !self.is_at_end() {
             if self.peek() == '\n' {
                 self.line += 1;
             }
+
+            // Skip the escaped character so \" and \{ don't end the segment
+            if self.peek() == '\\' {
+                self.advance();
+
+                if self.is_at_end() {
+                    break;
+                }
+            }
+
             self.advance();
         }
 
@@ -170,15 +335,21 @@ impl Lexer {
             return Err(format!("Unterminated string at line {}", self.line));
         }
 
-        // Consume the closing "
-        self.advance();
+        // Consume the closing " or the opening { of an interpolation
+        let terminator = self.advance();
 
-        // Trim the surrounding quotes
+        // Trim the surrounding delimiters
         let value: String = self.source[self.start + 1..self.current - 1].iter().collect();
         // Process escape sequences
         let value = self.process_escape_sequences(value)?;
 
-        self.add_token_literal(TokenType::String, Some(Literal::String(value)));
+        if terminator == '{' {
+            self.interpolations.push(0);
+            self.add_token_literal(TokenType::Interpolation, Some(Literal::String(value)));
+        } else {
+            self.add_token_literal(TokenType::String, Some(Literal::String(value)));
+        }
+
         Ok(())
     }
 
@@ -194,6 +365,8 @@ impl Lexer {
                     'r' => result.push('\r'),
                     '\\' => result.push('\\'),
                     '"' => result.push('"'),
+                    '{' => result.push('{'),
+                    '}' => result.push('}'),
                     c => return Err(format!("Invalid escape sequence \\{} at line {}", c, self.line)),
                 }
             } else {
@@ -204,6 +377,21 @@ impl Lexer {
         Ok(result)
     }
 
+    /// Consumes a `pmo` glued to the operator just scanned, as in `+pmo`.
+    fn match_pmo(&mut self) -> bool {
+        let end = self.current + 3;
+
+        if end > self.source.len()
+            || self.source[self.current..end] != ['p', 'm', 'o']
+            || (end < self.source.len() && self.is_alphanumeric(self.source[end]))
+        {
+            return false;
+        }
+
+        self.current = end;
+        true
+    }
+
     fn match_char(&mut self, expected: char) -> bool {
         if self.is_at_end() || self.source[self.current] != expected {
             return false;
@@ -230,11 +418,11 @@ impl Lexer {
     }
 
     fn is_alpha(&self, c: char) -> bool {
-        (c >= 'a' && c <= 'z') || (c >= 'A' && c <= 'Z') || c == '_'
+        c.is_ascii_alphabetic() || c == '_'
     }
 
     fn is_digit(&self, c: char) -> bool {
-        c >= '0' && c <= '9'
+        c.is_ascii_digit()
     }
 
     fn is_alphanumeric(&self, c: char) -> bool {
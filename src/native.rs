@@ -0,0 +1,65 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::environment::{Environment, NativeFunction, Value};
+use crate::interpreter::Interpreter;
+use crate::token::Literal;
+
+/// Builds the scope holding the built-in functions. Script globals live in a
+/// child of it, so user definitions shadow built-ins instead of replacing them.
+pub fn prelude() -> Environment {
+    let mut environment = Environment::new();
+
+    define(&mut environment, "len", 1, len);
+    define(&mut environment, "keys", 1, keys);
+    define(&mut environment, "has", 2, has);
+
+    environment
+}
+
+/// A fresh top-level scope for a script or a `summon`ed module, sitting on
+/// top of the prelude.
+pub fn global_environment() -> Rc<RefCell<Environment>> {
+    let prelude = Rc::new(RefCell::new(prelude()));
+    Rc::new(RefCell::new(Environment::new_enclosed(prelude)))
+}
+
+fn define(
+    environment: &mut Environment,
+    name: &'static str,
+    arity: usize,
+    function: fn(&mut Interpreter, Vec<Value>) -> Result<Value, String>,
+) {
+    environment.define(
+        name.to_string(),
+        Value::NativeFunction(NativeFunction { name, arity, function }),
+    );
+}
+
+fn len(_interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, String> {
+    let length = match &arguments[0] {
+        Value::Array(elements) => elements.len(),
+        Value::Map(map) => map.len(),
+        Value::Literal(Literal::String(s)) => s.chars().count(),
+        other => return Err(format!("Cannot take the length of {}.", other)),
+    };
+
+    Ok(Value::Literal(Literal::Integer(length as i64)))
+}
+
+fn keys(_interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, String> {
+    match &arguments[0] {
+        Value::Map(map) => Ok(Value::Array(Rc::new(map.keys().cloned().collect()))),
+        other => Err(format!("Cannot take the keys of {}.", other)),
+    }
+}
+
+fn has(_interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, String> {
+    match &arguments[0] {
+        Value::Map(map) => {
+            let found = map.get(&arguments[1])?.is_some();
+            Ok(Value::Literal(Literal::Boolean(found)))
+        },
+        other => Err(format!("Cannot look up keys in {}.", other)),
+    }
+}
@@ -1,13 +1,33 @@
+use std::rc::Rc;
+
 use crate::token::{Token, TokenType, Literal};
 
 #[derive(Debug, Clone)]
 pub enum Expr {
     Binary(Box<Expr>, Token, Box<Expr>),
+    Logical(Box<Expr>, Token, Box<Expr>),
+    Unary(Token, Box<Expr>),
     Grouping(Box<Expr>),
     Literal(Literal),
     Variable(Token),
-    Array(Token, Vec<Expr>),
+    Array(Vec<Expr>),
+    Map(Vec<(Expr, Expr)>),
     Call(Box<Expr>, Token, Vec<Expr>),
+    Index(Box<Expr>, Token, Box<Expr>),
+    Range(Box<Expr>, Token, Box<Expr>),
+    /// Pieces of an interpolated string, stringified and joined in order.
+    Interpolation(Vec<Expr>),
+    /// `target +pmo value` and friends, with the arithmetic operator.
+    CompoundAssign(Box<Expr>, Token, Box<Expr>),
+    /// `target++` or `target--`, with `+` or `-` as the operator. Evaluates
+    /// to the target's value from before the update.
+    Postfix(Box<Expr>, Token),
+    /// `module.name`, `record.field` or `instance.field`
+    Get(Box<Expr>, Token),
+    /// `og.method`, looking `method` up on the parent clique
+    Super(Token, Token),
+    /// `hawk(params) tuah <expr>`, or a `{ }`/`edge` body, used as a value.
+    Lambda(Token, Vec<Token>, Rc<Vec<Stmt>>),
 }
 
 #[derive(Debug, Clone)]
@@ -15,15 +35,87 @@ pub enum Stmt {
     Expression(Expr),
     Print(Expr),
     Var(Token, Option<Expr>),
-    If(Expr, Box<Stmt>, Option<Box<Stmt>>),
-    Loop(Option<Expr>, Vec<Stmt>),
-    Break,
-    Function(Token, Vec<Token>, Expr),
+    Block(Vec<Stmt>),
+    /// `yo`/`gurt yo` arms in source order, followed by the optional `gurt` branch.
+    If(Vec<(Expr, Stmt)>, Option<Box<Stmt>>),
+    Loop(Option<Token>, LoopKind, Vec<Stmt>),
+    Break(Option<Token>),
+    Continue(Option<Token>),
+    Delete(Expr),
+    Return(Token, Option<Expr>),
+    Function(Token, Vec<Token>, Rc<Vec<Stmt>>),
+    /// `vibe <subject> { <pattern> [yo <guard>] => <statement> ... }`
+    Match(Token, Expr, Vec<(Pattern, Option<Expr>, Stmt)>),
+    /// `crashout <value>;`
+    Throw(Token, Expr),
+    /// `bet { ... }`, then an optional `cope [name] { ... }` handler and an
+    /// optional `anyway { ... }` cleanup block.
+    Try(Vec<Stmt>, Option<(Option<Token>, Vec<Stmt>)>, Option<Vec<Stmt>>),
+    /// `squad Name { field, ... }`
+    Record(Token, Vec<Token>),
+    /// `clique Name [< Parent] { hawk ... }`; the methods are `Stmt::Function`s.
+    Class(Token, Option<Expr>, Vec<Stmt>),
+    /// `summon ...` of the module at the path held by the string token.
+    Import(Token, ImportKind),
+}
+
+#[derive(Debug, Clone)]
+pub enum ImportKind {
+    /// `summon "path" [as name];`, binding the whole module under one name.
+    /// Without `as`, the name is the file name minus its extension.
+    Namespace(Option<Token>),
+    /// `summon a, b from "path";`
+    Names(Vec<Token>),
 }
 
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// `_` matches anything without binding it.
+    Wildcard,
+    /// Any other name matches anything and binds it for the arm.
+    Binding(Token),
+    /// A string, number or boolean, compared with `==`.
+    Literal(Expr),
+    /// `start..end`, matching numbers with `start <= n < end`.
+    Range(Box<Expr>, Token, Box<Expr>),
+    /// `{p, q, ...}`, matching arrays of exactly that length.
+    Array(Vec<Pattern>),
+}
+
+#[derive(Debug, Clone)]
+pub enum LoopKind {
+    /// `goon ... edge`
+    Infinite,
+    /// `goon(n) ... edge`
+    Count(Expr),
+    /// `goon yo <condition> ... edge`
+    While(Expr),
+    /// `goon x in <iterable> ... edge`
+    Each(Token, Expr),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ClassKind {
+    None,
+    Class,
+    Subclass,
+}
+
+/// How deeply code may nest before parsing fails. Parsing and running
+/// recurse once per level, so this keeps both well inside the stack.
+const MAX_NESTING: usize = 256;
+
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    /// Labels of the loops enclosing the statement being parsed.
+    loop_labels: Vec<String>,
+    /// The kind of `clique` whose body is being parsed, which decides
+    /// whether `me` and `og` are allowed.
+    current_class: ClassKind,
+    /// How many expressions, statements and function bodies enclose the
+    /// current token.
+    depth: usize,
 }
 
 impl Parser {
@@ -31,6 +123,9 @@ impl Parser {
         Parser {
             tokens,
             current: 0,
+            loop_labels: Vec::new(),
+            current_class: ClassKind::None,
+            depth: 0,
         }
     }
 
@@ -47,13 +142,45 @@ impl Parser {
     fn declaration(&mut self) -> Result<Stmt, String> {
         if self.match_token(TokenType::Ts) {
             self.var_declaration()
-        } else if self.match_token(TokenType::Hawk) {
+        } else if self.match_token(TokenType::Summon) {
+            self.import_declaration()
+        } else if self.match_token(TokenType::Squad) {
+            self.record_declaration()
+        } else if self.match_token(TokenType::Clique) {
+            self.class_declaration()
+        } else if self.check(TokenType::Hawk) && self.check_next(TokenType::Identifier) {
+            self.advance();
             self.function_declaration()
+        } else if self.check(TokenType::Gyat) && self.check_next(TokenType::Identifier) {
+            self.advance();
+            self.array_declaration()
         } else {
             self.statement()
         }
     }
 
+    /// `gyat name {...};` is sugar for `ts name pmo {...};`.
+    fn array_declaration(&mut self) -> Result<Stmt, String> {
+        let name = self.consume(
+            TokenType::Identifier,
+            "Expected array name after 'gyat'.".to_string(),
+        )?;
+
+        self.consume(
+            TokenType::LeftBrace,
+            "Expected '{' after array name.".to_string(),
+        )?;
+
+        let elements = self.array_elements()?;
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after array declaration.".to_string(),
+        )?;
+
+        Ok(Stmt::Var(name, Some(Expr::Array(elements))))
+    }
+
     fn var_declaration(&mut self) -> Result<Stmt, String> {
         let name = self.consume(
             TokenType::Identifier,
@@ -85,6 +212,187 @@ impl Parser {
             "Expected '(' after function name.".to_string(),
         )?;
 
+        let parameters = self.parameters()?;
+
+        // Loop labels never reach across a function boundary
+        let enclosing_labels = std::mem::take(&mut self.loop_labels);
+        let body = self.function_body();
+        self.loop_labels = enclosing_labels;
+
+        Ok(Stmt::Function(name, parameters, Rc::new(body?)))
+    }
+
+    fn record_declaration(&mut self) -> Result<Stmt, String> {
+        let name = self.consume(
+            TokenType::Identifier,
+            "Expected record name after 'squad'.".to_string(),
+        )?;
+
+        self.consume(
+            TokenType::LeftBrace,
+            "Expected '{' after record name.".to_string(),
+        )?;
+
+        let mut fields: Vec<Token> = Vec::new();
+
+        if !self.check(TokenType::RightBrace) {
+            loop {
+                let field = self.consume(
+                    TokenType::Identifier,
+                    "Expected field name.".to_string(),
+                )?;
+
+                if fields.iter().any(|existing| existing.lexeme == field.lexeme) {
+                    return Err(format!(
+                        "Duplicate field '{}' in squad '{}' at line {}.",
+                        field.lexeme, name.lexeme, field.line
+                    ));
+                }
+
+                fields.push(field);
+
+                if !self.match_token(TokenType::Comma) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(
+            TokenType::RightBrace,
+            "Expected '}' after record fields.".to_string(),
+        )?;
+
+        Ok(Stmt::Record(name, fields))
+    }
+
+    fn class_declaration(&mut self) -> Result<Stmt, String> {
+        let name = self.consume(
+            TokenType::Identifier,
+            "Expected clique name.".to_string(),
+        )?;
+
+        let superclass = if self.match_token(TokenType::Less) {
+            let parent = self.call()?;
+
+            if let Expr::Variable(parent_name) = &parent
+                && parent_name.lexeme == name.lexeme
+            {
+                return Err(format!("A clique can't inherit from itself at line {}.", name.line));
+            }
+
+            Some(parent)
+        } else {
+            None
+        };
+
+        self.consume(
+            TokenType::LeftBrace,
+            "Expected '{' before clique body.".to_string(),
+        )?;
+
+        let enclosing_class = std::mem::replace(
+            &mut self.current_class,
+            if superclass.is_some() { ClassKind::Subclass } else { ClassKind::Class },
+        );
+        let methods = self.class_methods();
+        self.current_class = enclosing_class;
+
+        Ok(Stmt::Class(name, superclass, methods?))
+    }
+
+    fn class_methods(&mut self) -> Result<Vec<Stmt>, String> {
+        let mut methods = Vec::new();
+
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            self.consume(
+                TokenType::Hawk,
+                "Expected 'hawk' method in clique body.".to_string(),
+            )?;
+            methods.push(self.function_declaration()?);
+        }
+
+        self.consume(
+            TokenType::RightBrace,
+            "Expected '}' after clique body.".to_string(),
+        )?;
+
+        Ok(methods)
+    }
+
+    fn import_declaration(&mut self) -> Result<Stmt, String> {
+        // `as` and `from` are only special here, so they stay usable as names
+        let names = if self.check(TokenType::Identifier) {
+            let mut names = Vec::new();
+
+            loop {
+                names.push(self.consume(
+                    TokenType::Identifier,
+                    "Expected name to summon.".to_string(),
+                )?);
+
+                if !self.match_token(TokenType::Comma) {
+                    break;
+                }
+            }
+
+            if !self.match_contextual("from") {
+                return Err(format!("Expected 'from' after summoned names. Got {:?}", self.peek()));
+            }
+
+            Some(names)
+        } else {
+            None
+        };
+
+        let path = self.consume(
+            TokenType::String,
+            "Expected module path string after 'summon'.".to_string(),
+        )?;
+
+        let kind = match names {
+            Some(names) => ImportKind::Names(names),
+            None if self.match_contextual("as") => ImportKind::Namespace(Some(self.consume(
+                TokenType::Identifier,
+                "Expected name after 'as'.".to_string(),
+            )?)),
+            None => ImportKind::Namespace(None),
+        };
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after 'summon'.".to_string(),
+        )?;
+
+        Ok(Stmt::Import(path, kind))
+    }
+
+    /// Parses `hawk(params) ...` in expression position. Unlike a declaration,
+    /// the `tuah` form takes no `;` of its own, so the lambda can sit inside
+    /// a call or a collection literal.
+    fn lambda(&mut self) -> Result<Expr, String> {
+        let keyword = self.previous();
+
+        self.consume(
+            TokenType::LeftParen,
+            "Expected '(' after 'hawk'.".to_string(),
+        )?;
+
+        let parameters = self.parameters()?;
+
+        let enclosing_labels = std::mem::take(&mut self.loop_labels);
+        let body = if self.match_on_same_line(TokenType::Tuah) {
+            let tuah = self.previous();
+            self.expression().map(|value| vec![Stmt::Return(tuah, Some(value))])
+        } else {
+            self.function_body()
+        };
+        self.loop_labels = enclosing_labels;
+
+        Ok(Expr::Lambda(keyword, parameters, Rc::new(body?)))
+    }
+
+    /// Parses a parameter list after its opening `(`, up to and including `)`.
+    fn parameters(&mut self) -> Result<Vec<Token>, String> {
         let mut parameters = Vec::new();
 
         if !self.check(TokenType::RightParen) {
@@ -105,33 +413,95 @@ impl Parser {
             "Expected ')' after parameters.".to_string(),
         )?;
 
-        self.consume(
-            TokenType::Tuah,
-            "Expected 'tuah' after function parameters.".to_string(),
-        )?;
+        Ok(parameters)
+    }
+
+    fn function_body(&mut self) -> Result<Vec<Stmt>, String> {
+        self.nested(|parser| {
+            // Single-expression form: hawk name(params) tuah <expr>;
+            // A `tuah` on a later line instead starts a block body
+            if parser.match_on_same_line(TokenType::Tuah) {
+                let keyword = parser.previous();
+                let value = parser.expression()?;
+
+                parser.consume(
+                    TokenType::Semicolon,
+                    "Expected ';' after function body.".to_string(),
+                )?;
+
+                Ok(vec![Stmt::Return(keyword, Some(value))])
+            } else if parser.match_token(TokenType::LeftBrace) {
+                parser.block()
+            } else {
+                parser.edge_block("Expected 'edge' after function body.".to_string())
+            }
+        })
+    }
 
-        let body = self.expression()?;
+    fn block(&mut self) -> Result<Vec<Stmt>, String> {
+        let mut statements = Vec::new();
+
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            statements.push(self.declaration()?);
+        }
 
         self.consume(
-            TokenType::Semicolon,
-            "Expected ';' after function body.".to_string(),
+            TokenType::RightBrace,
+            "Expected '}' after block.".to_string(),
         )?;
 
-        Ok(Stmt::Function(name, parameters, body))
+        Ok(statements)
     }
 
-    fn statement(&mut self) -> Result<Stmt, String> {
-        if self.match_token(TokenType::Yap) {
-            self.print_statement()
-        } else if self.match_token(TokenType::Yo) {
-            self.if_statement()
-        } else if self.match_token(TokenType::Goon) {
-            self.loop_statement()
-        } else if self.match_token(TokenType::Sybau) {
-            self.break_statement()
-        } else {
-            self.expression_statement()
+    fn edge_block(&mut self, message: String) -> Result<Vec<Stmt>, String> {
+        let mut statements = Vec::new();
+
+        while !self.check(TokenType::Edge) && !self.is_at_end() {
+            statements.push(self.declaration()?);
         }
+
+        self.consume(TokenType::Edge, message)?;
+
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Stmt, String> {
+        self.nested(|parser| {
+            if parser.match_token(TokenType::Yap) {
+                parser.print_statement()
+            } else if parser.match_token(TokenType::Yo) {
+                parser.if_statement()
+            } else if parser.match_token(TokenType::Goon) {
+                parser.loop_statement(None)
+            } else if parser.check(TokenType::Identifier) && parser.check_next(TokenType::Colon) {
+                // label: goon ...
+                let label = parser.advance();
+                parser.advance();
+                parser.consume(
+                    TokenType::Goon,
+                    "Expected 'goon' after loop label.".to_string(),
+                )?;
+                parser.loop_statement(Some(label))
+            } else if parser.match_token(TokenType::Sybau) {
+                parser.break_statement()
+            } else if parser.match_token(TokenType::Skibidi) {
+                parser.continue_statement()
+            } else if parser.match_token(TokenType::Tuah) {
+                parser.return_statement()
+            } else if parser.match_token(TokenType::Ghost) {
+                parser.delete_statement()
+            } else if parser.match_token(TokenType::Vibe) {
+                parser.match_statement()
+            } else if parser.match_token(TokenType::Crashout) {
+                parser.throw_statement()
+            } else if parser.match_token(TokenType::Bet) {
+                parser.try_statement()
+            } else if parser.match_token(TokenType::LeftBrace) {
+                Ok(Stmt::Block(parser.block()?))
+            } else {
+                parser.expression_statement()
+            }
+        })
     }
 
     fn print_statement(&mut self) -> Result<Stmt, String> {
@@ -147,51 +517,293 @@ impl Parser {
 
     fn if_statement(&mut self) -> Result<Stmt, String> {
         let condition = self.expression()?;
+        let mut arms = vec![(condition, self.statement()?)];
+        let mut else_branch = None;
+
+        while self.match_token(TokenType::Gurt) {
+            // `gurt yo <cond>` continues the chain with another arm
+            if self.match_token(TokenType::Yo) {
+                let condition = self.expression()?;
+                arms.push((condition, self.statement()?));
+            } else {
+                else_branch = Some(Box::new(self.statement()?));
+                break;
+            }
+        }
+
+        Ok(Stmt::If(arms, else_branch))
+    }
+
+    fn match_statement(&mut self) -> Result<Stmt, String> {
+        let keyword = self.previous();
+        let subject = self.expression()?;
+
+        // Arms always sit in braces: without them, a first arm like
+        // `-1 => ...` would read as part of the subject
+        self.consume(
+            TokenType::LeftBrace,
+            "Expected '{' after 'vibe' subject.".to_string(),
+        )?;
+
+        let mut arms = Vec::new();
+
+        while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+            let pattern = self.pattern()?;
+
+            let guard = if self.match_token(TokenType::Yo) {
+                Some(self.expression()?)
+            } else {
+                None
+            };
+
+            self.consume(
+                TokenType::FatArrow,
+                "Expected '=>' after pattern.".to_string(),
+            )?;
 
-        let then_branch = Box::new(self.statement()?);
+            arms.push((pattern, guard, self.statement()?));
+        }
+
+        self.consume(
+            TokenType::RightBrace,
+            "Expected '}' after 'vibe' arms.".to_string(),
+        )?;
+
+        Ok(Stmt::Match(keyword, subject, arms))
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, String> {
+        if self.match_token(TokenType::LeftBrace) {
+            let mut elements = Vec::new();
+
+            if !self.check(TokenType::RightBrace) {
+                loop {
+                    elements.push(self.pattern()?);
+
+                    if !self.match_token(TokenType::Comma) {
+                        break;
+                    }
+                }
+            }
+
+            self.consume(
+                TokenType::RightBrace,
+                "Expected '}' after array pattern.".to_string(),
+            )?;
+
+            return Ok(Pattern::Array(elements));
+        }
+
+        if self.match_token(TokenType::Identifier) {
+            let name = self.previous();
+
+            return Ok(if name.lexeme == "_" {
+                Pattern::Wildcard
+            } else {
+                Pattern::Binding(name)
+            });
+        }
+
+        let start = self.pattern_literal()?;
+
+        if self.match_token(TokenType::DotDot) {
+            let operator = self.previous();
+            let end = self.pattern_literal()?;
+            return Ok(Pattern::Range(Box::new(start), operator, Box::new(end)));
+        }
 
-        let else_branch = if self.match_token(TokenType::Gurt) {
-            Some(Box::new(self.statement()?))
+        Ok(Pattern::Literal(start))
+    }
+
+    /// A literal, or a `-` directly in front of a number.
+    fn pattern_literal(&mut self) -> Result<Expr, String> {
+        if self.match_token(TokenType::Minus) {
+            let operator = self.previous();
+            let number = self.consume(
+                TokenType::Number,
+                "Expected number after '-' in pattern.".to_string(),
+            )?;
+
+            if let Some(literal) = number.literal {
+                return Ok(Expr::Binary(
+                    Box::new(Expr::Literal(Literal::Integer(0))),
+                    operator,
+                    Box::new(Expr::Literal(literal)),
+                ));
+            }
+        }
+
+        if self.match_tokens(&[TokenType::String, TokenType::Number, TokenType::Boolean])
+            && let Some(literal) = &self.previous().literal
+        {
+            return Ok(Expr::Literal(literal.clone()));
+        }
+
+        Err(format!("Expected pattern, got {:?}", self.peek()))
+    }
+
+    fn throw_statement(&mut self) -> Result<Stmt, String> {
+        let keyword = self.previous();
+        let value = self.expression()?;
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after 'crashout' value.".to_string(),
+        )?;
+
+        Ok(Stmt::Throw(keyword, value))
+    }
+
+    fn try_statement(&mut self) -> Result<Stmt, String> {
+        self.consume(
+            TokenType::LeftBrace,
+            "Expected '{' after 'bet'.".to_string(),
+        )?;
+
+        let body = self.block()?;
+
+        let handler = if self.match_token(TokenType::Cope) {
+            let name = if self.match_token(TokenType::Identifier) {
+                Some(self.previous())
+            } else {
+                None
+            };
+
+            self.consume(
+                TokenType::LeftBrace,
+                "Expected '{' after 'cope'.".to_string(),
+            )?;
+
+            Some((name, self.block()?))
         } else {
             None
         };
 
-        Ok(Stmt::If(condition, then_branch, else_branch))
-    }
+        let cleanup = if self.match_token(TokenType::Anyway) {
+            self.consume(
+                TokenType::LeftBrace,
+                "Expected '{' after 'anyway'.".to_string(),
+            )?;
 
-    fn loop_statement(&mut self) -> Result<Stmt, String> {
-        let mut condition = None;
+            Some(self.block()?)
+        } else {
+            None
+        };
 
-        // Check if it's a goon(n) style loop
-        if self.match_token(TokenType::LeftParen) {
-            condition = Some(self.expression()?);
+        if handler.is_none() && cleanup.is_none() {
+            return Err(format!("Expected 'cope' or 'anyway' after 'bet' block. Got {:?}", self.peek()));
+        }
+
+        Ok(Stmt::Try(body, handler, cleanup))
+    }
+
+    fn loop_statement(&mut self, label: Option<Token>) -> Result<Stmt, String> {
+        // Headers must start on the `goon` line; on the next line, `yo ...`
+        // or `(...)` is the first statement of an infinite loop's body
+        let kind = if self.on_same_line() && self.check(TokenType::Identifier) && self.check_next(TokenType::In) {
+            // goon x in <iterable>
+            let name = self.advance();
+            self.advance();
+            LoopKind::Each(name, self.expression()?)
+        } else if self.match_on_same_line(TokenType::Yo) {
+            // goon yo <condition>
+            LoopKind::While(self.expression()?)
+        } else if self.match_on_same_line(TokenType::LeftParen) {
+            // goon(n)
+            let count = self.expression()?;
             self.consume(
                 TokenType::RightParen,
-                "Expected ')' after loop condition.".to_string(),
+                "Expected ')' after loop count.".to_string(),
             )?;
+            LoopKind::Count(count)
+        } else {
+            LoopKind::Infinite
+        };
+
+        if let Some(label) = &label {
+            self.loop_labels.push(label.lexeme.clone());
         }
 
-        let mut body = Vec::new();
+        let body = self.edge_block("Expected 'edge' after loop body.".to_string());
 
-        while !self.check(TokenType::Edge) && !self.is_at_end() {
-            body.push(self.declaration()?);
+        if label.is_some() {
+            self.loop_labels.pop();
         }
 
+        Ok(Stmt::Loop(label, kind, body?))
+    }
+
+    fn break_statement(&mut self) -> Result<Stmt, String> {
+        let label = self.jump_label()?;
+
         self.consume(
-            TokenType::Edge,
-            "Expected 'edge' after loop body.".to_string(),
+            TokenType::Semicolon,
+            "Expected ';' after 'sybau'.".to_string(),
         )?;
 
-        Ok(Stmt::Loop(condition, body))
+        Ok(Stmt::Break(label))
     }
 
-    fn break_statement(&mut self) -> Result<Stmt, String> {
+    fn continue_statement(&mut self) -> Result<Stmt, String> {
+        let label = self.jump_label()?;
+
         self.consume(
             TokenType::Semicolon,
-            "Expected ';' after 'sybau'.".to_string(),
+            "Expected ';' after 'skibidi'.".to_string(),
         )?;
 
-        Ok(Stmt::Break)
+        Ok(Stmt::Continue(label))
+    }
+
+    /// Parses the optional loop label after `sybau`/`skibidi`, which must name
+    /// one of the enclosing loops.
+    fn jump_label(&mut self) -> Result<Option<Token>, String> {
+        if !self.match_token(TokenType::Identifier) {
+            return Ok(None);
+        }
+
+        let label = self.previous();
+
+        if !self.loop_labels.contains(&label.lexeme) {
+            return Err(format!(
+                "Unknown loop label '{}' at line {}.",
+                label.lexeme, label.line
+            ));
+        }
+
+        Ok(Some(label))
+    }
+
+    fn delete_statement(&mut self) -> Result<Stmt, String> {
+        let target = self.expression()?;
+
+        if !matches!(target, Expr::Index(..)) {
+            return Err("Expected an indexed element after 'ghost'.".to_string());
+        }
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after 'ghost' target.".to_string(),
+        )?;
+
+        Ok(Stmt::Delete(target))
+    }
+
+    fn return_statement(&mut self) -> Result<Stmt, String> {
+        let keyword = self.previous();
+
+        let value = if self.check(TokenType::Semicolon) {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+
+        self.consume(
+            TokenType::Semicolon,
+            "Expected ';' after return value.".to_string(),
+        )?;
+
+        Ok(Stmt::Return(keyword, value))
     }
 
     fn expression_statement(&mut self) -> Result<Stmt, String> {
@@ -206,92 +818,242 @@ impl Parser {
     }
 
     fn expression(&mut self) -> Result<Expr, String> {
-        self.assignment()
+        self.nested(Self::assignment)
+    }
+
+    /// Runs `parse` one level deeper, failing instead of recursing further
+    /// once `MAX_NESTING` levels are open. Expressions, statements and
+    /// function bodies each count as a level.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        if self.depth >= MAX_NESTING {
+            return Err(format!("Code is nested too deeply at line {}.", self.peek().line));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+
+        result
     }
 
     fn assignment(&mut self) -> Result<Expr, String> {
-        let expr = self.equality()?;
+        let expr = self.or()?;
 
         if self.match_token(TokenType::Pmo) {
-            let value = self.assignment()?;
+            let equals = self.previous();
+            let value = self.nested(Self::assignment)?;
 
-            if let Expr::Variable(name) = expr {
-                return Ok(Expr::Binary(Box::new(Expr::Variable(name)),
-                                      self.previous(),
+            if let Expr::Variable(_) | Expr::Index(..) | Expr::Get(..) = expr {
+                return Ok(Expr::Binary(Box::new(expr),
+                                      equals,
                                       Box::new(value)));
             }
 
             return Err("Invalid assignment target.".to_string());
         }
 
+        if self.match_tokens(&[
+            TokenType::PlusPmo,
+            TokenType::MinusPmo,
+            TokenType::StarPmo,
+            TokenType::SlashPmo,
+            TokenType::ModuloPmo,
+            TokenType::StarStarPmo,
+            TokenType::TildeSlashPmo,
+            TokenType::AmpersandPmo,
+            TokenType::PipePmo,
+            TokenType::CaretPmo,
+            TokenType::LessLessPmo,
+            TokenType::GreaterGreaterPmo,
+        ]) {
+            let operator = self.previous();
+            let value = self.nested(Self::assignment)?;
+            return self.compound_assignment(expr, operator, value);
+        }
+
         Ok(expr)
     }
 
-    fn equality(&mut self) -> Result<Expr, String> {
-        let mut expr = self.comparison()?;
+    /// Builds `target +pmo value` and friends, keeping just the operator the
+    /// update applies.
+    fn compound_assignment(&self, target: Expr, operator: Token, value: Expr) -> Result<Expr, String> {
+        if !is_assignable(&target) {
+            return Err("Invalid assignment target.".to_string());
+        }
 
-        while self.match_tokens(&[TokenType::Equal, TokenType::NotEqual]) {
-            let operator = self.previous();
-            let right = self.comparison()?;
-            expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
+        let (token_type, lexeme) = match operator.token_type {
+            TokenType::PlusPmo => (TokenType::Plus, "+"),
+            TokenType::MinusPmo => (TokenType::Minus, "-"),
+            TokenType::StarPmo => (TokenType::Star, "*"),
+            TokenType::SlashPmo => (TokenType::Slash, "/"),
+            TokenType::ModuloPmo => (TokenType::Modulo, "%"),
+            TokenType::StarStarPmo => (TokenType::StarStar, "**"),
+            TokenType::TildeSlashPmo => (TokenType::TildeSlash, "~/"),
+            TokenType::AmpersandPmo => (TokenType::Ampersand, "&"),
+            TokenType::PipePmo => (TokenType::Pipe, "|"),
+            TokenType::CaretPmo => (TokenType::Caret, "^"),
+            TokenType::LessLessPmo => (TokenType::LessLess, "<<"),
+            _ => (TokenType::GreaterGreater, ">>"),
+        };
+
+        let binary = Token::new(token_type, lexeme.to_string(), None, operator.line);
+
+        Ok(Expr::CompoundAssign(Box::new(target), binary, Box::new(value)))
+    }
+
+    /// Builds `target++` or `target--` from the operator's first token.
+    fn postfix(&self, target: Expr, operator: Token) -> Result<Expr, String> {
+        if !is_assignable(&target) {
+            return Err("Invalid assignment target.".to_string());
         }
 
-        Ok(expr)
+        let (token_type, lexeme) = if operator.token_type == TokenType::PlusPlus {
+            (TokenType::Plus, "+")
+        } else {
+            (TokenType::Minus, "-")
+        };
+
+        let binary = Token::new(token_type, lexeme.to_string(), None, operator.line);
+
+        Ok(Expr::Postfix(Box::new(target), binary))
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        self.left_associative(&[TokenType::Ong], Self::and, Expr::Logical)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        self.left_associative(&[TokenType::Fr], Self::equality, Expr::Logical)
+    }
+
+    fn equality(&mut self) -> Result<Expr, String> {
+        self.left_associative(&[TokenType::Equal, TokenType::NotEqual], Self::comparison, Expr::Binary)
     }
 
     fn comparison(&mut self) -> Result<Expr, String> {
-        let mut expr = self.term()?;
+        self.left_associative(
+            &[
+                TokenType::Greater,
+                TokenType::GreaterEqual,
+                TokenType::Less,
+                TokenType::LessEqual,
+            ],
+            Self::range,
+            Expr::Binary,
+        )
+    }
 
-        while self.match_tokens(&[
-            TokenType::Greater,
-            TokenType::GreaterEqual,
-            TokenType::Less,
-            TokenType::LessEqual,
-        ]) {
+    fn range(&mut self) -> Result<Expr, String> {
+        let expr = self.bit_or()?;
+
+        if self.match_token(TokenType::DotDot) {
             let operator = self.previous();
-            let right = self.term()?;
-            expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
+            let end = self.bit_or()?;
+            return Ok(Expr::Range(Box::new(expr), operator, Box::new(end)));
         }
 
         Ok(expr)
     }
 
-    fn term(&mut self) -> Result<Expr, String> {
-        let mut expr = self.factor()?;
+    fn bit_or(&mut self) -> Result<Expr, String> {
+        self.left_associative(&[TokenType::Pipe], Self::bit_xor, Expr::Binary)
+    }
 
-        while self.match_tokens(&[TokenType::Plus, TokenType::Minus]) {
-            let operator = self.previous();
-            let right = self.factor()?;
-            expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
-        }
+    fn bit_xor(&mut self) -> Result<Expr, String> {
+        self.left_associative(&[TokenType::Caret], Self::bit_and, Expr::Binary)
+    }
 
-        Ok(expr)
+    fn bit_and(&mut self) -> Result<Expr, String> {
+        self.left_associative(&[TokenType::Ampersand], Self::shift, Expr::Binary)
+    }
+
+    fn shift(&mut self) -> Result<Expr, String> {
+        self.left_associative(&[TokenType::LessLess, TokenType::GreaterGreater], Self::term, Expr::Binary)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        self.left_associative(&[TokenType::Plus, TokenType::Minus], Self::factor, Expr::Binary)
     }
 
     fn factor(&mut self) -> Result<Expr, String> {
-        let mut expr = self.unary()?;
+        self.left_associative(
+            &[
+                TokenType::Star,
+                TokenType::Slash,
+                TokenType::TildeSlash,
+                TokenType::Modulo,
+            ],
+            Self::unary,
+            Expr::Binary,
+        )
+    }
+
+    /// Parses `operand (operator operand)*`, grouping to the left. Each
+    /// operator puts the tree one level deeper, so a long run of them counts
+    /// toward `MAX_NESTING` just like parentheses do.
+    fn left_associative(
+        &mut self,
+        operators: &[TokenType],
+        operand: fn(&mut Self) -> Result<Expr, String>,
+        combine: fn(Box<Expr>, Token, Box<Expr>) -> Expr,
+    ) -> Result<Expr, String> {
+        let mut expr = operand(self)?;
+        let depth = self.depth;
+
+        let result = loop {
+            if !self.match_tokens(operators) {
+                break Ok(expr);
+            }
 
-        while self.match_tokens(&[TokenType::Star, TokenType::Slash, TokenType::Modulo]) {
             let operator = self.previous();
-            let right = self.unary()?;
-            expr = Expr::Binary(Box::new(expr), operator, Box::new(right));
-        }
+            if self.depth >= MAX_NESTING {
+                break Err(format!("Code is nested too deeply at line {}.", operator.line));
+            }
+            self.depth += 1;
 
-        Ok(expr)
+            match operand(self) {
+                Ok(right) => expr = combine(Box::new(expr), operator, Box::new(right)),
+                Err(e) => break Err(e),
+            }
+        };
+
+        self.depth = depth;
+        result
     }
 
     fn unary(&mut self) -> Result<Expr, String> {
+        if self.match_tokens(&[TokenType::Nah, TokenType::Tilde]) {
+            let operator = self.previous();
+            let right = self.nested(Self::unary)?;
+            return Ok(Expr::Unary(operator, Box::new(right)));
+        }
+
         if self.match_tokens(&[TokenType::Minus]) {
             let operator = self.previous();
-            let right = self.unary()?;
+            let right = self.nested(Self::unary)?;
             return Ok(Expr::Binary(
-                Box::new(Expr::Literal(Literal::Number(0.0))),
+                Box::new(Expr::Literal(Literal::Integer(0))),
                 operator,
                 Box::new(right),
             ));
         }
 
-        self.call()
+        self.power()
+    }
+
+    /// `**` binds tighter than unary minus on its left and is
+    /// right-associative, so `-2 ** 2` is `-(2 ** 2)` and `2 ** 3 ** 2` is
+    /// `2 ** (3 ** 2)`.
+    fn power(&mut self) -> Result<Expr, String> {
+        let expr = self.call()?;
+
+        if self.match_token(TokenType::StarStar) {
+            let operator = self.previous();
+            let right = self.nested(Self::unary)?;
+            return Ok(Expr::Binary(Box::new(expr), operator, Box::new(right)));
+        }
+
+        Ok(expr)
     }
 
     fn call(&mut self) -> Result<Expr, String> {
@@ -300,11 +1062,44 @@ impl Parser {
         loop {
             if self.match_token(TokenType::LeftParen) {
                 expr = self.finish_call(expr)?;
+            } else if self.match_token(TokenType::LeftBracket) {
+                let index = self.expression()?;
+                let bracket = self.consume(
+                    TokenType::RightBracket,
+                    "Expected ']' after index.".to_string(),
+                )?;
+                expr = Expr::Index(Box::new(expr), bracket, Box::new(index));
+            } else if self.match_token(TokenType::Dot) {
+                let name = self.consume(
+                    TokenType::Identifier,
+                    "Expected name after '.'.".to_string(),
+                )?;
+                expr = Expr::Get(Box::new(expr), name);
             } else {
                 break;
             }
         }
 
+        if self.match_token(TokenType::PlusPlus) {
+            let operator = self.previous();
+            return self.postfix(expr, operator);
+        }
+
+        // `x--` is a decrement only when no operand follows; `a--b` and
+        // `--x` keep meaning `a - (-b)` and `-(-x)`. A third minus after a
+        // variable is a subtraction from it, so `x-- - 1` decrements `x`
+        let decrements = match self.tokens.get(self.current + 2) {
+            Some(token) if token.token_type == TokenType::Minus => is_assignable(&expr),
+            Some(token) => !starts_operand(&token.token_type),
+            None => true,
+        };
+
+        if self.check(TokenType::Minus) && self.check_next(TokenType::Minus) && decrements {
+            let operator = self.advance();
+            self.advance();
+            return self.postfix(expr, operator);
+        }
+
         Ok(expr)
     }
 
@@ -330,10 +1125,12 @@ impl Parser {
     }
 
     fn primary(&mut self) -> Result<Expr, String> {
-        if self.match_token(TokenType::String) || self.match_token(TokenType::Number) {
+        if self.match_tokens(&[TokenType::String, TokenType::Number, TokenType::Boolean]) {
             if let Some(literal) = &self.previous().literal {
                 return Ok(Expr::Literal(literal.clone()));
             }
+        } else if self.match_token(TokenType::Interpolation) {
+            return self.interpolation();
         } else if self.match_token(TokenType::Identifier) {
             return Ok(Expr::Variable(self.previous()));
         } else if self.match_token(TokenType::LeftParen) {
@@ -343,8 +1140,47 @@ impl Parser {
                 "Expected ')' after expression.".to_string(),
             )?;
             return Ok(Expr::Grouping(Box::new(expr)));
+        } else if self.match_token(TokenType::LeftBrace) {
+            return self.collection();
         } else if self.match_token(TokenType::Gyat) {
-            return self.array();
+            self.consume(
+                TokenType::LeftBrace,
+                "Expected '{' after 'gyat'.".to_string(),
+            )?;
+            return Ok(Expr::Array(self.array_elements()?));
+        } else if self.match_token(TokenType::Hawk) {
+            return self.lambda();
+        } else if self.match_token(TokenType::Me) {
+            let keyword = self.previous();
+
+            if self.current_class == ClassKind::None {
+                return Err(format!("Can't use 'me' outside of a clique method at line {}.", keyword.line));
+            }
+
+            return Ok(Expr::Variable(keyword));
+        } else if self.match_token(TokenType::Og) {
+            let keyword = self.previous();
+
+            match self.current_class {
+                ClassKind::None => {
+                    return Err(format!("Can't use 'og' outside of a clique method at line {}.", keyword.line));
+                },
+                ClassKind::Class => {
+                    return Err(format!("Can't use 'og' in a clique with no parent at line {}.", keyword.line));
+                },
+                ClassKind::Subclass => {},
+            }
+
+            self.consume(
+                TokenType::Dot,
+                "Expected '.' after 'og'.".to_string(),
+            )?;
+            let method = self.consume(
+                TokenType::Identifier,
+                "Expected parent method name.".to_string(),
+            )?;
+
+            return Ok(Expr::Super(keyword, method));
         } else if self.match_token(TokenType::Yeet) {
             return Ok(Expr::Literal(Literal::String("__YEET__".to_string())));  // Special marker for input
         }
@@ -352,19 +1188,94 @@ impl Parser {
         Err(format!("Expected expression, got {:?}", self.peek()))
     }
 
-    fn array(&mut self) -> Result<Expr, String> {
-        // Consume the array name
-        let name = self.consume(
-            TokenType::Identifier,
-            "Expected array name after 'gyat'.".to_string()
+    /// Parses `"a {x} b {y} c"`, which the lexer hands over as
+    /// `Interpolation("a ") x Interpolation(" b ") y String(" c")`.
+    fn interpolation(&mut self) -> Result<Expr, String> {
+        let mut parts = Vec::new();
+
+        loop {
+            if let Some(Literal::String(segment)) = self.previous().literal
+                && !segment.is_empty()
+            {
+                parts.push(Expr::Literal(Literal::String(segment)));
+            }
+
+            parts.push(self.expression()?);
+
+            if !self.match_token(TokenType::Interpolation) {
+                break;
+            }
+        }
+
+        let end = self.consume(
+            TokenType::String,
+            "Expected '}' after interpolated expression.".to_string(),
         )?;
 
-        // Consume the opening brace
+        if let Some(Literal::String(segment)) = end.literal
+            && !segment.is_empty()
+        {
+            parts.push(Expr::Literal(Literal::String(segment)));
+        }
+
+        Ok(Expr::Interpolation(parts))
+    }
+
+    /// Parses a `{...}` literal, which is a map when the first element is
+    /// followed by ':' (or is just ':' for the empty map) and an array
+    /// otherwise.
+    fn collection(&mut self) -> Result<Expr, String> {
+        if self.match_token(TokenType::Colon) {
+            self.consume(
+                TokenType::RightBrace,
+                "Expected '}' after ':' in empty map.".to_string(),
+            )?;
+            return Ok(Expr::Map(Vec::new()));
+        }
+
+        if self.check(TokenType::RightBrace) {
+            return Ok(Expr::Array(self.array_elements()?));
+        }
+
+        let first = self.expression()?;
+
+        if !self.match_token(TokenType::Colon) {
+            let mut elements = vec![first];
+
+            if self.match_token(TokenType::Comma) {
+                elements.extend(self.array_elements()?);
+            } else {
+                self.consume(
+                    TokenType::RightBrace,
+                    "Expected '}' after array elements.".to_string(),
+                )?;
+            }
+
+            return Ok(Expr::Array(elements));
+        }
+
+        let mut entries = vec![(first, self.expression()?)];
+
+        while self.match_token(TokenType::Comma) {
+            let key = self.expression()?;
+            self.consume(
+                TokenType::Colon,
+                "Expected ':' after map key.".to_string(),
+            )?;
+            entries.push((key, self.expression()?));
+        }
+
         self.consume(
-            TokenType::LeftBrace,
-            "Expected '{' after array name.".to_string(),
+            TokenType::RightBrace,
+            "Expected '}' after map entries.".to_string(),
         )?;
 
+        Ok(Expr::Map(entries))
+    }
+
+    /// Parses the elements of an array literal; the opening '{' has already
+    /// been consumed.
+    fn array_elements(&mut self) -> Result<Vec<Expr>, String> {
         let mut elements = Vec::new();
 
         if !self.check(TokenType::RightBrace) {
@@ -382,7 +1293,7 @@ impl Parser {
             "Expected '}' after array elements.".to_string(),
         )?;
 
-        Ok(Expr::Array(name, elements))
+        Ok(elements)
     }
 
     fn match_token(&mut self, token_type: TokenType) -> bool {
@@ -410,6 +1321,13 @@ impl Parser {
         self.peek().token_type == token_type
     }
 
+    fn check_next(&self, token_type: TokenType) -> bool {
+        match self.tokens.get(self.current + 1) {
+            Some(token) => token.token_type == token_type,
+            None => false,
+        }
+    }
+
     fn advance(&mut self) -> Token {
         if !self.is_at_end() {
             self.current += 1;
@@ -429,6 +1347,31 @@ impl Parser {
         self.tokens[self.current - 1].clone()
     }
 
+    fn on_same_line(&self) -> bool {
+        self.peek().line == self.previous().line
+    }
+
+    /// Matches `token_type` only if it sits on the same line as the token
+    /// before it.
+    fn match_on_same_line(&mut self, token_type: TokenType) -> bool {
+        if self.on_same_line() && self.check(token_type) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Matches an identifier that acts as a keyword in this one spot.
+    fn match_contextual(&mut self, word: &str) -> bool {
+        if self.check(TokenType::Identifier) && self.peek().lexeme == word {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
     fn consume(&mut self, token_type: TokenType, message: String) -> Result<Token, String> {
         if self.check(token_type) {
             Ok(self.advance())
@@ -437,3 +1380,128 @@ impl Parser {
         }
     }
 }
+
+/// Whether `pmo`, `++` and friends can store into `expr`.
+fn is_assignable(expr: &Expr) -> bool {
+    matches!(expr, Expr::Variable(_) | Expr::Index(..) | Expr::Get(..))
+}
+
+/// Whether a token can begin an operand, as `primary` or a prefix operator.
+fn starts_operand(token_type: &TokenType) -> bool {
+    matches!(
+        token_type,
+        TokenType::Identifier
+            | TokenType::Number
+            | TokenType::String
+            | TokenType::Boolean
+            | TokenType::Interpolation
+            | TokenType::LeftParen
+            | TokenType::LeftBrace
+            | TokenType::Gyat
+            | TokenType::Yeet
+            | TokenType::Hawk
+            | TokenType::Me
+            | TokenType::Og
+            | TokenType::Minus
+            | TokenType::Nah
+            | TokenType::Tilde
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::Lexer;
+
+    fn parse(source: &str) -> Vec<Stmt> {
+        let tokens = Lexer::new(source.to_string()).scan_tokens().unwrap();
+        Parser::new(tokens).parse().unwrap()
+    }
+
+    #[test]
+    fn goon_yo_on_one_line_is_a_while_loop() {
+        let statements = parse("goon yo n < 3\n    n++;\nedge");
+
+        assert!(matches!(&statements[0], Stmt::Loop(None, LoopKind::While(_), body) if body.len() == 1));
+    }
+
+    #[test]
+    fn yo_on_the_line_after_goon_starts_the_body() {
+        let statements = parse("goon\n    yo n == 3 sybau;\n    n++;\nedge");
+
+        let Stmt::Loop(None, LoopKind::Infinite, body) = &statements[0] else {
+            panic!("expected an infinite loop, got {:?}", statements[0]);
+        };
+        assert!(matches!(body[0], Stmt::If(..)));
+        assert_eq!(body.len(), 2);
+    }
+
+    #[test]
+    fn parenthesis_on_the_line_after_goon_starts_the_body() {
+        let statements = parse("goon\n    (n);\n    sybau;\nedge");
+
+        assert!(matches!(&statements[0], Stmt::Loop(None, LoopKind::Infinite, body) if body.len() == 2));
+    }
+
+    #[test]
+    fn double_minus_is_a_decrement_only_without_an_operand_after_it() {
+        let statements = parse("x--;\n5--3;");
+
+        assert!(matches!(&statements[0], Stmt::Expression(Expr::Postfix(..))));
+        assert!(matches!(&statements[1], Stmt::Expression(Expr::Binary(..))));
+    }
+
+    #[test]
+    fn minus_after_a_decrement_subtracts_from_it() {
+        let statements = parse("x-- - 1;\n5---1;");
+
+        let Stmt::Expression(Expr::Binary(left, _, _)) = &statements[0] else {
+            panic!("expected a subtraction, got {:?}", statements[0]);
+        };
+        assert!(matches!(**left, Expr::Postfix(..)));
+
+        let Stmt::Expression(Expr::Binary(left, _, _)) = &statements[1] else {
+            panic!("expected a subtraction, got {:?}", statements[1]);
+        };
+        assert!(matches!(**left, Expr::Literal(_)));
+    }
+
+    #[test]
+    fn every_binary_operator_has_a_pmo_form() {
+        let statements = parse("x **pmo 2; x ~/pmo 2; x &pmo 1; x |pmo 1; x ^pmo 1; x <<pmo 1; x >>pmo 1;");
+
+        let operators: Vec<&str> = statements
+            .iter()
+            .map(|statement| match statement {
+                Stmt::Expression(Expr::CompoundAssign(_, operator, _)) => operator.lexeme.as_str(),
+                other => panic!("expected a compound assignment, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(operators, ["**", "~/", "&", "|", "^", "<<", ">>"]);
+    }
+
+    #[test]
+    fn deep_nesting_is_a_parse_error_instead_of_a_stack_overflow() {
+        // Reaching the limit takes more than a test thread's default stack
+        // in a debug build; the interpreter itself runs on a bigger one too
+        let check = || {
+            let parens = format!("yap {}1{};", "(".repeat(100_000), ")".repeat(100_000));
+            let chain = format!("yap 1{};", " + 1".repeat(100_000));
+
+            for source in [parens, chain] {
+                let tokens = Lexer::new(source).scan_tokens().unwrap();
+                let error = Parser::new(tokens).parse().unwrap_err();
+                assert!(error.starts_with("Code is nested too deeply"), "{}", error);
+            }
+
+            parse(&format!("yap {}1{};", "(".repeat(100), ")".repeat(100)));
+        };
+
+        std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(check)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+}
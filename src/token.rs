@@ -1,16 +1,26 @@
-#[allow(dead_code)]
+use crate::bigint::BigInt;
+
+#[allow(dead_code, clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     // Single-character tokens
     LeftParen, RightParen, LeftBrace, RightBrace,
-    Comma, Semicolon,
+    LeftBracket, RightBracket, Comma, Colon, Semicolon, Dot, DotDot,
 
     // Operators
     Plus, Minus, Star, Slash, Modulo,
+    StarStar, TildeSlash,
+    Ampersand, Pipe, Caret, Tilde, LessLess, GreaterGreater,
+    PlusPmo, MinusPmo, StarPmo, SlashPmo, ModuloPmo, // +pmo, -pmo, ...
+    StarStarPmo, TildeSlashPmo, // **pmo, ~/pmo
+    AmpersandPmo, PipePmo, CaretPmo, LessLessPmo, GreaterGreaterPmo, // &pmo, |pmo, ...
+    PlusPlus,
+    FatArrow, // =>
     Equal, NotEqual, Greater, GreaterEqual, Less, LessEqual,
 
     // Literals
     Identifier, String, Number, Boolean,
+    Interpolation, // string segment followed by an interpolated `{expr}`
 
     // Keywords
     Yap,      // println!()
@@ -23,8 +33,24 @@ pub enum TokenType {
     Edge,     // end of loop
     Yeet,     // read input
     Sybau,    // break
+    Skibidi,  // continue
     Yo,       // if
     Gurt,     // else
+    Fr,       // and
+    Ong,      // or
+    Nah,      // not
+    Ghost,    // remove an element
+    In,       // for-each source
+    Vibe,     // match
+    Crashout, // throw
+    Bet,      // try
+    Cope,     // catch
+    Anyway,   // finally
+    Summon,   // import
+    Squad,    // record type
+    Clique,   // class
+    Me,       // self
+    Og,       // super
 
     EOF
 }
@@ -52,7 +78,10 @@ impl Token {
 #[derive(Debug, Clone)]
 pub enum Literal {
     String(String),
-    Number(f64),
+    Integer(i64),
+    /// Integers outside the `i64` range.
+    BigInt(BigInt),
+    Float(f64),
     Boolean(bool),
     Nil,
 }
@@ -61,7 +90,12 @@ impl std::fmt::Display for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Literal::String(s) => write!(f, "{}", s),
-            Literal::Number(n) => write!(f, "{}", n),
+            Literal::Integer(n) => write!(f, "{}", n),
+            Literal::BigInt(n) => write!(f, "{}", n),
+            // `{:?}` keeps a trailing ".0" on whole floats, and writes huge
+            // ones as "1e20", so they never print like integers
+            Literal::Float(n) if n.fract() == 0.0 => write!(f, "{:?}", n),
+            Literal::Float(n) => write!(f, "{}", n),
             Literal::Boolean(b) => write!(f, "{}", b),
             Literal::Nil => write!(f, "nil"),
         }